@@ -0,0 +1,82 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use crate::glyphs::GlyphMode;
+use crate::models::SpoilerLevel;
+use crate::theme::ThemeName;
+use crate::tui::{Keymap, Preferences};
+
+// Every field is optional so that a config file, the command line and the
+// built-in defaults can be layered on top of each other.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub title: Option<String>,
+    pub edition: Option<String>,
+    pub spoilers: Option<SpoilerLevel>,
+    pub theme: Option<ThemeName>,
+    pub glyphs: Option<GlyphMode>,
+    pub keymap: Option<Keymap>,
+    pub collapse_completed: Option<bool>,
+}
+
+impl Config {
+    pub fn default_path() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir().context("could not determine the user config directory")?;
+        Ok(config_dir.join("sciadv").join("config.toml"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err).with_context(|| format!("failed to read {}", path.display())),
+        };
+        toml::from_str(&contents).with_context(|| format!("invalid config file {}", path.display()))
+    }
+
+    // Values set here win over the ones in `fallback`.
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            title: self.title.or(fallback.title),
+            edition: self.edition.or(fallback.edition),
+            spoilers: self.spoilers.or(fallback.spoilers),
+            theme: self.theme.or(fallback.theme),
+            glyphs: self.glyphs.or(fallback.glyphs),
+            keymap: self.keymap.or(fallback.keymap),
+            collapse_completed: self.collapse_completed.or(fallback.collapse_completed),
+        }
+    }
+
+    // Fills in everything that has a default, detecting the theme and glyphs
+    // from the environment, so the result is what the TUI will actually use.
+    pub fn resolved(self) -> Self {
+        Self {
+            spoilers: Some(self.spoilers.unwrap_or_default()),
+            theme: Some(ThemeName::from_env(self.theme)),
+            glyphs: Some(GlyphMode::from_env(self.glyphs)),
+            keymap: Some(self.keymap.unwrap_or_default()),
+            collapse_completed: Some(self.collapse_completed.unwrap_or_default()),
+            ..self
+        }
+    }
+
+    pub fn preferences(&self) -> Preferences {
+        Preferences {
+            spoilers: self.spoilers.unwrap_or_default(),
+            theme: self.theme.unwrap_or_default(),
+            glyphs: self.glyphs.unwrap_or_default(),
+            keymap: self.keymap.unwrap_or_default(),
+            collapse_completed: self.collapse_completed.unwrap_or_default(),
+        }
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests;
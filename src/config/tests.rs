@@ -0,0 +1,61 @@
+use super::*;
+use crate::testing::TempDir;
+
+fn temp_config(name: &str, contents: &str) -> (TempDir, PathBuf) {
+    let dir = TempDir::new(name);
+    let path = dir.write("config.toml", contents);
+    (dir, path)
+}
+
+#[test]
+fn missing_file_loads_as_an_empty_config() {
+    let dir = TempDir::new("config-missing");
+    assert_eq!(Config::load(&dir.join("config.toml")).unwrap(), Config::default());
+}
+
+#[test]
+fn config_files_use_kebab_case_keys() {
+    let (_dir, path) = temp_config("config-keys", "title = \"chaos_head\"\nspoilers = \"routes\"\ntheme = \"high-contrast\"\ncollapse-completed = true\n");
+    let config = Config::load(&path).unwrap();
+
+    assert_eq!(config.title.as_deref(), Some("chaos_head"));
+    assert_eq!(config.spoilers, Some(SpoilerLevel::Routes));
+    assert_eq!(config.theme, Some(ThemeName::HighContrast));
+    assert_eq!(config.collapse_completed, Some(true));
+    assert_eq!(config.glyphs, None);
+}
+
+#[test]
+fn unknown_keys_are_errors() {
+    let (_dir, path) = temp_config("config-unknown", "title = \"chaos_head\"\ncolour = \"red\"\n");
+    let err = format!("{:#}", Config::load(&path).unwrap_err());
+
+    assert!(err.contains(&path.display().to_string()), "{err}");
+    assert!(err.contains("unknown field `colour`"), "{err}");
+}
+
+#[test]
+fn command_line_values_override_the_file() {
+    let file = Config {
+        title: Some("chaos_head".to_string()),
+        theme: Some(ThemeName::Light),
+        keymap: Some(Keymap::Vim),
+        ..Config::default()
+    };
+    let cli = Config { theme: Some(ThemeName::ColorBlind), ..Config::default() };
+    let config = cli.or(file);
+
+    assert_eq!(config.title.as_deref(), Some("chaos_head"));
+    assert_eq!(config.theme, Some(ThemeName::ColorBlind));
+    assert_eq!(config.preferences().keymap, Keymap::Vim);
+}
+
+#[test]
+fn resolved_configs_round_trip_through_toml() {
+    let config = Config { title: Some("chaos_head".to_string()), ..Config::default() }.resolved();
+    assert_eq!(config.spoilers, Some(SpoilerLevel::Off));
+    assert!(config.glyphs.is_some());
+
+    let parsed: Config = toml::from_str(&config.to_toml().unwrap()).unwrap();
+    assert_eq!(parsed, config);
+}
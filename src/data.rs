@@ -1,5 +1,100 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use crate::flags::DELUSIONS;
 use crate::models::*;
 
+const NOAH: &str = "noah";
+const ORIGINAL: &str = "original";
+
+// The first edition is the default.
+pub fn editions(title: &str) -> &'static [Edition] {
+    match title {
+        "chaos_head" => &[
+            Edition { id: NOAH, name: "Chaos;Head NoAH" },
+            Edition { id: ORIGINAL, name: "Chaos;Head" },
+        ],
+        _ => &[],
+    }
+}
+
+pub fn load_title(title: &str) -> Option<Vec<Route>> {
+    match title {
+        "chaos_head" => Some(create_chaos_head_data()),
+        _ => None,
+    }
+}
+
+// Only lists checked against the game itself belong here, and no title's
+// trophy list has been transcribed that way yet.
+pub fn achievements(_title: &str) -> Vec<Achievement> {
+    Vec::new()
+}
+
+// The same goes for TIPS: the guide mentions where they appear, but the
+// entries themselves haven't been transcribed.
+pub fn tips(_title: &str) -> Vec<Tip> {
+    Vec::new()
+}
+
+// Every route ends in one of the title's endings, so the ending list follows
+// from the guide; gallery and movie lists haven't been transcribed.
+pub fn collection(title: &str) -> Vec<CollectionItem> {
+    load_title(title)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|route| CollectionItem {
+            id: format!("ending_{}", slug(&route.name)),
+            name: route.name.clone(),
+            kind: CollectionKind::Ending,
+            unlock: Unlock::Route(route.name),
+            editions: route.editions,
+        })
+        .collect()
+}
+
+fn slug(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+// Lists the player supplies for a title in `<title>.extras.json` next to the
+// progress files, added to whatever is bundled.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Extras {
+    pub achievements: Vec<Achievement>,
+    pub tips: Vec<Tip>,
+}
+
+impl Extras {
+    pub fn path(title: &str) -> Result<PathBuf> {
+        let data_dir = dirs::data_dir().context("could not determine the user data directory")?;
+        Ok(data_dir.join("sciadv").join(format!("{title}.extras.json")))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read extras file {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse extras file {}", path.display()))
+    }
+}
+
+pub fn load_guide_file(path: &Path) -> Result<Vec<Route>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read guide file {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("failed to parse guide file {}", path.display()))
+}
+
 pub fn create_chaos_head_data() -> Vec<Route> {
     vec![
         create_silent_sky_route(),
@@ -19,6 +114,8 @@ fn create_silent_sky_route() -> Route {
         name: "Silent Sky".to_string(),
         description: "The first ending - complete the basic story path".to_string(),
         prerequisites: vec![],
+        requires: vec![],
+        editions: vec![],
         chapters: vec![
             Chapter {
                 number: 1,
@@ -31,6 +128,10 @@ fn create_silent_sky_route() -> Route {
                             instruction: "Series of YES/NO questions about school swimsuits".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -45,6 +146,10 @@ fn create_silent_sky_route() -> Route {
                             instruction: "Checklist questions when Takumi visits hospital - results appear in TIPS menu".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -59,32 +164,44 @@ fn create_silent_sky_route() -> Route {
                             save_point: "Before O-FRONT YES/NO questions".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "silent_sky_ch6_bad_ending".to_string(),
                         description: "Optional: View bad ending (NO, NO, NO)".to_string(),
-                        step_type: StepType::YesNoPrompts {
+                        step_type: StepType::Choices {
                             prompts: vec![
-                                PromptChoice { question: "First O-FRONT question".to_string(), answer: false },
-                                PromptChoice { question: "Second O-FRONT question".to_string(), answer: false },
-                                PromptChoice { question: "Third O-FRONT question".to_string(), answer: false },
+                                ChoicePrompt::yes_no("First O-FRONT question", false),
+                                ChoicePrompt::yes_no("Second O-FRONT question", false),
+                                ChoicePrompt::yes_no("Third O-FRONT question", false),
                             ],
                         },
                         completed: false,
+                        optional: true,
+                        reload_from: Some("silent_sky_ch6_save".to_string()),
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "silent_sky_ch6_proceed".to_string(),
                         description: "Proceed with story (YES, YES, NO, NO, YES)".to_string(),
-                        step_type: StepType::YesNoPrompts {
+                        step_type: StepType::Choices {
                             prompts: vec![
-                                PromptChoice { question: "First O-FRONT question".to_string(), answer: true },
-                                PromptChoice { question: "Second O-FRONT question".to_string(), answer: true },
-                                PromptChoice { question: "Third O-FRONT question".to_string(), answer: false },
-                                PromptChoice { question: "Fourth O-FRONT question".to_string(), answer: false },
-                                PromptChoice { question: "Fifth O-FRONT question".to_string(), answer: true },
+                                ChoicePrompt::yes_no("First O-FRONT question", true),
+                                ChoicePrompt::yes_no("Second O-FRONT question", true),
+                                ChoicePrompt::yes_no("Third O-FRONT question", false),
+                                ChoicePrompt::yes_no("Fourth O-FRONT question", false),
+                                ChoicePrompt::yes_no("Fifth O-FRONT question", true),
                             ],
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: Some("silent_sky_ch6_save".to_string()),
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -99,6 +216,10 @@ fn create_silent_sky_route() -> Route {
                             instruction: "Silent Sky ending will occur during this chapter".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -111,6 +232,8 @@ fn create_crying_sky_route() -> Route {
         name: "Crying Sky".to_string(),
         description: "View at least 11 delusions throughout the story".to_string(),
         prerequisites: vec!["Silent Sky".to_string()],
+        requires: vec![Condition::Counter { counter: DELUSIONS.to_string(), at_least: Some(11), at_most: None }],
+        editions: vec![],
         chapters: vec![
             Chapter {
                 number: 0,
@@ -123,6 +246,10 @@ fn create_crying_sky_route() -> Route {
                             instruction: "Use Skip mode to fast-forward through read text. Answer NO to all heroine YES/NO prompts.".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -139,6 +266,10 @@ fn create_crying_sky_route() -> Route {
                             location: "While in class, talking to Misumi".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "crying_sky_dt3".to_string(),
@@ -149,6 +280,10 @@ fn create_crying_sky_route() -> Route {
                             location: "Yua is looking at you from across the street".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -165,6 +300,10 @@ fn create_crying_sky_route() -> Route {
                             location: "During the Phantasm performance, while watching FES".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "crying_sky_dt9".to_string(),
@@ -175,6 +314,10 @@ fn create_crying_sky_route() -> Route {
                             location: "After the Phantasm performance, while playing ESO".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -187,6 +330,8 @@ fn create_daydream_route() -> Route {
         name: "Daydream".to_string(),
         description: "Nanami's route - focus on her character choices".to_string(),
         prerequisites: vec!["Crying Sky".to_string()],
+        requires: vec![],
+        editions: vec![NOAH.to_string()],
         chapters: vec![
             Chapter {
                 number: 1,
@@ -201,6 +346,10 @@ fn create_daydream_route() -> Route {
                             location: "While in class, talking to Misumi".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "daydream_dt2".to_string(),
@@ -211,20 +360,28 @@ fn create_daydream_route() -> Route {
                             location: "In the Base with Nanami".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "daydream_nanami_choices".to_string(),
                         description: "Nanami relationship choices".to_string(),
-                        step_type: StepType::YesNoPrompts {
+                        step_type: StepType::Choices {
                             prompts: vec![
-                                PromptChoice { question: "I like big sister types more.".to_string(), answer: false },
-                                PromptChoice { question: "I like girls with no chests.".to_string(), answer: true },
-                                PromptChoice { question: "I have another 3D girlfriend besides you.".to_string(), answer: false },
-                                PromptChoice { question: "You're cute, I'll admit that.".to_string(), answer: true },
-                                PromptChoice { question: "I want to kiss you!".to_string(), answer: true },
+                                ChoicePrompt::yes_no("I like big sister types more.", false),
+                                ChoicePrompt::yes_no("I like girls with no chests.", true),
+                                ChoicePrompt::yes_no("I have another 3D girlfriend besides you.", false),
+                                ChoicePrompt::yes_no("You're cute, I'll admit that.", true),
+                                ChoicePrompt::yes_no("I want to kiss you!", true),
                             ],
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -239,6 +396,10 @@ fn create_daydream_route() -> Route {
                             instruction: "Game will branch into Daydream ending in chapter six".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -251,6 +412,8 @@ fn create_moon_and_sun_route() -> Route {
         name: "Moon and Sun".to_string(),
         description: "Yua's route - focus on her character choices".to_string(),
         prerequisites: vec!["Crying Sky".to_string()],
+        requires: vec![],
+        editions: vec![NOAH.to_string()],
         chapters: vec![
             Chapter {
                 number: 1,
@@ -265,20 +428,28 @@ fn create_moon_and_sun_route() -> Route {
                             location: "While in class, talking to Misumi".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "moon_sun_yua_choices".to_string(),
                         description: "Yua relationship choices".to_string(),
-                        step_type: StepType::YesNoPrompts {
+                        step_type: StepType::Choices {
                             prompts: vec![
-                                PromptChoice { question: "It's just a coincidence.".to_string(), answer: false },
-                                PromptChoice { question: "I'm hallucinating.".to_string(), answer: false },
-                                PromptChoice { question: "Whoever did it is inside the school.".to_string(), answer: true },
-                                PromptChoice { question: "They're taunting me.".to_string(), answer: true },
-                                PromptChoice { question: "I want to erase it now.".to_string(), answer: true },
+                                ChoicePrompt::yes_no("It's just a coincidence.", false),
+                                ChoicePrompt::yes_no("I'm hallucinating.", false),
+                                ChoicePrompt::yes_no("Whoever did it is inside the school.", true),
+                                ChoicePrompt::yes_no("They're taunting me.", true),
+                                ChoicePrompt::yes_no("I want to erase it now.", true),
                             ],
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -293,6 +464,10 @@ fn create_moon_and_sun_route() -> Route {
                             instruction: "Game will branch into Moon and Sun ending in chapter seven".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -305,6 +480,8 @@ fn create_bloody_contract_route() -> Route {
         name: "A Bloody Contract for Your Sins".to_string(),
         description: "Ayase's route - focus on her character choices".to_string(),
         prerequisites: vec!["Crying Sky".to_string()],
+        requires: vec![],
+        editions: vec![NOAH.to_string()],
         chapters: vec![
             Chapter {
                 number: 1,
@@ -319,6 +496,10 @@ fn create_bloody_contract_route() -> Route {
                             location: "While in class, talking to Misumi".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "bloody_contract_dt2".to_string(),
@@ -329,6 +510,10 @@ fn create_bloody_contract_route() -> Route {
                             location: "In the Base with Nanami".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -345,20 +530,28 @@ fn create_bloody_contract_route() -> Route {
                             location: "During the Phantasm performance, while watching FES".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "bloody_contract_ayase_choices".to_string(),
                         description: "Ayase relationship choices".to_string(),
-                        step_type: StepType::YesNoPrompts {
+                        step_type: StepType::Choices {
                             prompts: vec![
-                                PromptChoice { question: "FES wants me to save her.".to_string(), answer: true },
-                                PromptChoice { question: "I want to give in to her temptation.".to_string(), answer: false },
-                                PromptChoice { question: "It would feel so good to jump.".to_string(), answer: true },
-                                PromptChoice { question: "I'd be willing to die if I could do it with FES.".to_string(), answer: true },
-                                PromptChoice { question: "I want to be released from everything.".to_string(), answer: true },
+                                ChoicePrompt::yes_no("FES wants me to save her.", true),
+                                ChoicePrompt::yes_no("I want to give in to her temptation.", false),
+                                ChoicePrompt::yes_no("It would feel so good to jump.", true),
+                                ChoicePrompt::yes_no("I'd be willing to die if I could do it with FES.", true),
+                                ChoicePrompt::yes_no("I want to be released from everything.", true),
                             ],
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -373,6 +566,10 @@ fn create_bloody_contract_route() -> Route {
                             instruction: "Game will branch into A Bloody Contract for Your Sins ending in chapter seven".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -385,6 +582,8 @@ fn create_disease_slaughter_route() -> Route {
         name: "A Disease that Leads to Slaughter".to_string(),
         description: "Kozue's route - focus on her character choices".to_string(),
         prerequisites: vec!["Crying Sky".to_string()],
+        requires: vec![],
+        editions: vec![NOAH.to_string()],
         chapters: vec![
             Chapter {
                 number: 1,
@@ -399,6 +598,10 @@ fn create_disease_slaughter_route() -> Route {
                             location: "While in class, talking to Misumi".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "disease_slaughter_dt2".to_string(),
@@ -409,6 +612,10 @@ fn create_disease_slaughter_route() -> Route {
                             location: "In the Base with Nanami".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -425,6 +632,10 @@ fn create_disease_slaughter_route() -> Route {
                             location: "During the Phantasm performance, while watching FES".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -441,6 +652,10 @@ fn create_disease_slaughter_route() -> Route {
                             location: "At Center Street with Sena".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "disease_slaughter_dt13".to_string(),
@@ -451,20 +666,28 @@ fn create_disease_slaughter_route() -> Route {
                             location: "In the classroom, Kozue has just joined your class".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "disease_slaughter_kozue_choices".to_string(),
                         description: "Kozue relationship choices".to_string(),
-                        step_type: StepType::YesNoPrompts {
+                        step_type: StepType::Choices {
                             prompts: vec![
-                                PromptChoice { question: "She came to save me?".to_string(), answer: true },
-                                PromptChoice { question: "The transfer student is a professional exorcist.".to_string(), answer: true },
-                                PromptChoice { question: "Her wimpy looks are actually camouflage.".to_string(), answer: true },
-                                PromptChoice { question: "She is here to kill Rimi.".to_string(), answer: true },
-                                PromptChoice { question: "You two kill each other!".to_string(), answer: false },
+                                ChoicePrompt::yes_no("She came to save me?", true),
+                                ChoicePrompt::yes_no("The transfer student is a professional exorcist.", true),
+                                ChoicePrompt::yes_no("Her wimpy looks are actually camouflage.", true),
+                                ChoicePrompt::yes_no("She is here to kill Rimi.", true),
+                                ChoicePrompt::yes_no("You two kill each other!", false),
                             ],
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -479,6 +702,10 @@ fn create_disease_slaughter_route() -> Route {
                             instruction: "Game will branch into A Disease that Leads to Slaughter ending in chapter seven".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -491,6 +718,8 @@ fn create_deus_ex_machina_route() -> Route {
         name: "Deus Ex Machina".to_string(),
         description: "Sena's route - focus on her character choices".to_string(),
         prerequisites: vec!["Crying Sky".to_string()],
+        requires: vec![],
+        editions: vec![NOAH.to_string()],
         chapters: vec![
             Chapter {
                 number: 1,
@@ -505,6 +734,10 @@ fn create_deus_ex_machina_route() -> Route {
                             location: "While in class, talking to Misumi".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "deus_ex_machina_dt2".to_string(),
@@ -515,6 +748,10 @@ fn create_deus_ex_machina_route() -> Route {
                             location: "In the Base with Nanami".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -531,6 +768,10 @@ fn create_deus_ex_machina_route() -> Route {
                             location: "During the Phantasm performance, while watching FES".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -547,20 +788,28 @@ fn create_deus_ex_machina_route() -> Route {
                             location: "At Center Street with Sena".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "deus_ex_machina_sena_choices".to_string(),
                         description: "Sena relationship choices".to_string(),
-                        step_type: StepType::YesNoPrompts {
+                        step_type: StepType::Choices {
                             prompts: vec![
-                                PromptChoice { question: "Look at me more.".to_string(), answer: true },
-                                PromptChoice { question: "For her, I wouldn't mind begging on my knees.".to_string(), answer: true },
-                                PromptChoice { question: "I wish she'd step on me, actually.".to_string(), answer: true },
-                                PromptChoice { question: "That sword's going to run me through, isn't it? I can tell.".to_string(), answer: true },
-                                PromptChoice { question: "She's going to kill me with that sword…".to_string(), answer: false },
+                                ChoicePrompt::yes_no("Look at me more.", true),
+                                ChoicePrompt::yes_no("For her, I wouldn't mind begging on my knees.", true),
+                                ChoicePrompt::yes_no("I wish she'd step on me, actually.", true),
+                                ChoicePrompt::yes_no("That sword's going to run me through, isn't it? I can tell.", true),
+                                ChoicePrompt::yes_no("She's going to kill me with that sword…", false),
                             ],
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -575,6 +824,10 @@ fn create_deus_ex_machina_route() -> Route {
                             instruction: "Game will branch into Deus Ex Machina ending in chapter seven".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -587,6 +840,8 @@ fn create_anima_archetype_route() -> Route {
         name: "Anima Archetype".to_string(),
         description: "Rimi's route - focus on her character choices".to_string(),
         prerequisites: vec!["Crying Sky".to_string()],
+        requires: vec![],
+        editions: vec![NOAH.to_string()],
         chapters: vec![
             Chapter {
                 number: 1,
@@ -601,20 +856,28 @@ fn create_anima_archetype_route() -> Route {
                             location: "While in class, talking to Misumi".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "anima_archetype_rimi_choices".to_string(),
                         description: "Rimi appearance choices".to_string(),
-                        step_type: StepType::YesNoPrompts {
+                        step_type: StepType::Choices {
                             prompts: vec![
-                                PromptChoice { question: "…tied her hair with ribbons.".to_string(), answer: true },
-                                PromptChoice { question: "…wore glasses.".to_string(), answer: false },
-                                PromptChoice { question: "…wore over-the-knee socks.".to_string(), answer: true },
-                                PromptChoice { question: "…wore a long skirt.".to_string(), answer: false },
-                                PromptChoice { question: "…was posing like a soldier at attention.".to_string(), answer: true },
+                                ChoicePrompt::yes_no("…tied her hair with ribbons.", true),
+                                ChoicePrompt::yes_no("…wore glasses.", false),
+                                ChoicePrompt::yes_no("…wore over-the-knee socks.", true),
+                                ChoicePrompt::yes_no("…wore a long skirt.", false),
+                                ChoicePrompt::yes_no("…was posing like a soldier at attention.", true),
                             ],
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -629,6 +892,10 @@ fn create_anima_archetype_route() -> Route {
                             instruction: "Game will branch into Anima Archetype ending in chapter eight".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                 ],
             },
@@ -650,6 +917,8 @@ fn create_blue_sky_route() -> Route {
             "Deus Ex Machina".to_string(),
             "Anima Archetype".to_string(),
         ],
+        requires: vec![],
+        editions: vec![],
         chapters: vec![
             Chapter {
                 number: 0,
@@ -662,30 +931,38 @@ fn create_blue_sky_route() -> Route {
                             instruction: "New main menu option unlocked after completing all other routes".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "blue_sky_final_choices".to_string(),
                         description: "Final YES/NO sequence".to_string(),
-                        step_type: StepType::YesNoPrompts {
+                        step_type: StepType::Choices {
                             prompts: vec![
-                                PromptChoice { question: "Question 1".to_string(), answer: true },
-                                PromptChoice { question: "Question 2".to_string(), answer: true },
-                                PromptChoice { question: "Question 3".to_string(), answer: false },
-                                PromptChoice { question: "Question 4".to_string(), answer: false },
-                                PromptChoice { question: "Question 5".to_string(), answer: false },
-                                PromptChoice { question: "Question 6".to_string(), answer: true },
-                                PromptChoice { question: "Question 7".to_string(), answer: true },
-                                PromptChoice { question: "Question 8".to_string(), answer: true },
-                                PromptChoice { question: "Question 9".to_string(), answer: true },
-                                PromptChoice { question: "Question 10".to_string(), answer: true },
-                                PromptChoice { question: "Question 11".to_string(), answer: true },
-                                PromptChoice { question: "Question 12".to_string(), answer: true },
-                                PromptChoice { question: "Question 13".to_string(), answer: true },
-                                PromptChoice { question: "Question 14".to_string(), answer: true },
-                                PromptChoice { question: "Question 15".to_string(), answer: true },
+                                ChoicePrompt::yes_no("Question 1", true),
+                                ChoicePrompt::yes_no("Question 2", true),
+                                ChoicePrompt::yes_no("Question 3", false),
+                                ChoicePrompt::yes_no("Question 4", false),
+                                ChoicePrompt::yes_no("Question 5", false),
+                                ChoicePrompt::yes_no("Question 6", true),
+                                ChoicePrompt::yes_no("Question 7", true),
+                                ChoicePrompt::yes_no("Question 8", true),
+                                ChoicePrompt::yes_no("Question 9", true),
+                                ChoicePrompt::yes_no("Question 10", true),
+                                ChoicePrompt::yes_no("Question 11", true),
+                                ChoicePrompt::yes_no("Question 12", true),
+                                ChoicePrompt::yes_no("Question 13", true),
+                                ChoicePrompt::yes_no("Question 14", true),
+                                ChoicePrompt::yes_no("Question 15", true),
                             ],
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![],
                     },
                     Step {
                         id: "blue_sky_ending".to_string(),
@@ -694,9 +971,27 @@ fn create_blue_sky_route() -> Route {
                             instruction: "Achieve Blue Sky ending and complete the game".to_string(),
                         },
                         completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![NOAH.to_string()],
+                    },
+                    Step {
+                        id: "blue_sky_ending_original".to_string(),
+                        description: "Complete Chaos;Head".to_string(),
+                        step_type: StepType::GeneralInstruction {
+                            instruction: "Achieve Blue Sky ending and complete the game".to_string(),
+                        },
+                        completed: false,
+                        optional: false,
+                        reload_from: None,
+                        effects: vec![],
+                        editions: vec![ORIGINAL.to_string()],
                     },
                 ],
             },
         ],
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests;
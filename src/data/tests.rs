@@ -0,0 +1,57 @@
+use super::*;
+use crate::testing::TempDir;
+
+fn temp_file(name: &str, contents: Option<&str>) -> (TempDir, PathBuf) {
+    let dir = TempDir::new(name);
+    let path = match contents {
+        Some(contents) => dir.write("chaos_head.extras.json", contents),
+        None => dir.join("chaos_head.extras.json"),
+    };
+    (dir, path)
+}
+
+#[test]
+fn missing_extras_are_empty() {
+    let (_dir, path) = temp_file("extras-missing", None);
+    let extras = Extras::load(&path).unwrap();
+    assert!(extras.achievements.is_empty());
+    assert!(extras.tips.is_empty());
+}
+
+#[test]
+fn extras_add_achievements_to_the_title() {
+    let (_dir, path) = temp_file("extras", Some(r#"{
+        "achievements": [
+            { "id": "swimsuit", "name": "Swimsuit", "description": "", "unlock": { "Step": "silent_sky_ch1_swimsuit" } },
+            { "id": "original_only", "name": "Original", "description": "", "editions": ["original"] }
+        ]
+    }"#));
+    let extras = Extras::load(&path).unwrap();
+    let mut game = crate::load_title("chaos_head").unwrap().with_achievements(extras.achievements);
+
+    assert_eq!(game.achievements.len(), 1);
+    game.mark_step_completed("silent_sky_ch1_swimsuit", true);
+    assert_eq!(game.achievement_progress(), (1, 1));
+}
+
+#[test]
+fn extras_add_tips_to_the_title() {
+    let (_dir, path) = temp_file("extras-tips", Some(r#"{
+        "tips": [
+            { "id": "gigalomaniac", "term": "Gigalomaniac", "chapter": 2 },
+            { "id": "crying", "term": "Crying", "chapter": 1, "routes": ["Crying Sky"] }
+        ]
+    }"#));
+    let extras = Extras::load(&path).unwrap();
+    let game = crate::load_title("chaos_head").unwrap().with_tips(extras.tips);
+
+    assert_eq!(game.tip_chapters(), [1, 2]);
+    assert_eq!(game.tip_progress(), (0, 2));
+}
+
+#[test]
+fn unknown_extras_keys_report_the_file() {
+    let (_dir, path) = temp_file("extras-unknown", Some(r#"{ "trophies": [] }"#));
+    let error = format!("{:#}", Extras::load(&path).unwrap_err());
+    assert!(error.contains("failed to parse extras file"), "{error}");
+}
@@ -0,0 +1,225 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use crate::models::*;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlagState {
+    pub flags: BTreeSet<String>,
+    pub counters: BTreeMap<String, i32>,
+}
+
+impl FlagState {
+    pub fn apply(&mut self, change: &FlagChange) {
+        match change {
+            FlagChange::Set(flag) => {
+                self.flags.insert(flag.clone());
+            }
+            FlagChange::Clear(flag) => {
+                self.flags.remove(flag);
+            }
+            FlagChange::Add { counter, amount } => {
+                *self.counters.entry(counter.clone()).or_default() += amount;
+            }
+        }
+    }
+
+    pub fn counter(&self, counter: &str) -> i32 {
+        self.counters.get(counter).copied().unwrap_or_default()
+    }
+
+    pub fn satisfies(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Flag(flag) => self.flags.contains(flag),
+            Condition::NotFlag(flag) => !self.flags.contains(flag),
+            Condition::Counter { counter, at_least, at_most } => {
+                let value = self.counter(counter);
+                at_least.is_none_or(|min| value >= min) && at_most.is_none_or(|max| value <= max)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reachability {
+    pub route_idx: usize,
+    pub blocked_by: Vec<Condition>,
+}
+
+impl Reachability {
+    pub fn is_reachable(&self) -> bool {
+        self.blocked_by.is_empty()
+    }
+}
+
+pub fn trigger_flag(trigger_number: u32, polarity: &Polarity) -> String {
+    format!("dt{trigger_number}_{}", polarity.name().to_lowercase())
+}
+
+pub fn delusion_counter(polarity: &Polarity) -> String {
+    format!("{}_delusions", polarity.name().to_lowercase())
+}
+
+// Delusions the player actually watched: every trigger answered Positive or
+// Negative, whichever way it went.
+pub const DELUSIONS: &str = "delusions";
+
+// The options the guide tells the player to take at this step, used until
+// the player records what they actually picked.
+pub fn guide_choices(step: &Step) -> Vec<String> {
+    match &step.step_type {
+        StepType::DelusionTrigger { polarity, .. } => vec![polarity.name().to_string()],
+        StepType::Choices { prompts } => prompts.iter()
+            .flat_map(|prompt| prompt.pick.options())
+            .cloned()
+            .collect(),
+        StepType::GeneralInstruction { .. } | StepType::Checkpoint { .. } => vec![],
+    }
+}
+
+// Every delusion trigger implicitly sets `dt<N>_<polarity>` and bumps
+// `<polarity>_delusions` (and `delusions` unless it was Neutral), on top of
+// whatever the guide lists explicitly.
+pub fn step_changes(step: &Step, chosen: &[String]) -> Vec<FlagChange> {
+    let mut changes = Vec::new();
+    if let StepType::DelusionTrigger { trigger_number, .. } = &step.step_type {
+        for polarity in Polarity::ALL.iter().filter(|p| chosen.iter().any(|c| c == p.name())) {
+            changes.push(FlagChange::Set(trigger_flag(*trigger_number, polarity)));
+            changes.push(FlagChange::Add { counter: delusion_counter(polarity), amount: 1 });
+            if !matches!(polarity, Polarity::Neutral) {
+                changes.push(FlagChange::Add { counter: DELUSIONS.to_string(), amount: 1 });
+            }
+        }
+    }
+    changes.extend(step.effects.iter()
+        .filter(|effect| effect.when.as_ref().is_none_or(|when| chosen.contains(when)))
+        .map(|effect| effect.change.clone()));
+    changes
+}
+
+// Everything a step could do, whichever option the player ends up taking.
+pub fn possible_changes(step: &Step) -> Vec<FlagChange> {
+    let mut changes = Vec::new();
+    if let StepType::DelusionTrigger { trigger_number, .. } = &step.step_type {
+        for polarity in &Polarity::ALL {
+            changes.push(FlagChange::Set(trigger_flag(*trigger_number, polarity)));
+            changes.push(FlagChange::Add { counter: delusion_counter(polarity), amount: 1 });
+        }
+        // A trigger is answered once, so it adds at most one delusion.
+        changes.push(FlagChange::Add { counter: DELUSIONS.to_string(), amount: 1 });
+    }
+    changes.extend(step.effects.iter().map(|effect| effect.change.clone()));
+    changes
+}
+
+// A route's ending needs its own delusion triggers taken the way the guide
+// says, plus anything it lists explicitly.
+pub fn route_conditions(route: &Route) -> Vec<Condition> {
+    let mut conditions: Vec<Condition> = route.chapters.iter()
+        .flat_map(|chapter| &chapter.steps)
+        .filter_map(|step| match &step.step_type {
+            StepType::DelusionTrigger { trigger_number, polarity, .. } => {
+                Some(Condition::Flag(trigger_flag(*trigger_number, polarity)))
+            }
+            _ => None,
+        })
+        .collect();
+    conditions.extend(route.requires.iter().cloned());
+    conditions
+}
+
+// Steps that are the same decision in every route's checklist: a delusion
+// trigger can only be answered once per playthrough.
+fn decision_key(step: &Step) -> String {
+    match &step.step_type {
+        StepType::DelusionTrigger { trigger_number, .. } => format!("dt{trigger_number}"),
+        _ => step.id.clone(),
+    }
+}
+
+fn completed_steps(route: &Route) -> impl Iterator<Item = (u32, &Step)> {
+    route.chapters.iter()
+        .flat_map(|chapter| chapter.steps.iter().map(move |step| (chapter.number, step)))
+        .filter(|(_, step)| step.completed)
+}
+
+impl GameData {
+    pub fn flag_state(&self, route_idx: usize) -> FlagState {
+        let mut state = FlagState::default();
+        if let Some(route) = self.routes.get(route_idx) {
+            for (_, step) in completed_steps(route) {
+                let chosen = self.choice(&step.id).map_or_else(|| guide_choices(step), <[_]>::concat);
+                for change in step_changes(step, &chosen) {
+                    state.apply(&change);
+                }
+            }
+        }
+        state
+    }
+
+    // Which endings can still be reached from the playthrough tracked by
+    // `route_idx`, assuming the player follows that ending's guide from the
+    // current chapter onwards.
+    pub fn reachable_endings(&self, route_idx: usize) -> Vec<Reachability> {
+        let Some(playthrough) = self.routes.get(route_idx) else {
+            return vec![];
+        };
+        let state = self.flag_state(route_idx);
+        let current_chapter = completed_steps(playthrough).map(|(number, _)| number).max();
+        let decided: HashSet<String> = completed_steps(playthrough)
+            .map(|(_, step)| decision_key(step))
+            .collect();
+
+        self.routes.iter().enumerate().map(|(idx, route)| {
+            let future: Vec<FlagChange> = route.chapters.iter()
+                .filter(|chapter| current_chapter.is_none_or(|current| chapter.number >= current))
+                .flat_map(|chapter| &chapter.steps)
+                .filter(|step| !decided.contains(&decision_key(step)))
+                .flat_map(possible_changes)
+                .collect();
+
+            Reachability {
+                route_idx: idx,
+                blocked_by: route_conditions(route).into_iter()
+                    .filter(|condition| !can_satisfy(&state, &future, condition))
+                    .collect(),
+            }
+        }).collect()
+    }
+
+    pub fn is_ending_reachable(&self, route_idx: usize) -> bool {
+        self.ending_blockers(route_idx).is_empty()
+    }
+
+    // What stands between the playthrough tracked by `route_idx` and its own
+    // ending.
+    pub fn ending_blockers(&self, route_idx: usize) -> Vec<Condition> {
+        self.reachable_endings(route_idx)
+            .into_iter()
+            .find(|reachability| reachability.route_idx == route_idx)
+            .map_or_else(Vec::new, |reachability| reachability.blocked_by)
+    }
+}
+
+fn can_satisfy(state: &FlagState, future: &[FlagChange], condition: &Condition) -> bool {
+    if state.satisfies(condition) {
+        return true;
+    }
+
+    match condition {
+        Condition::Flag(flag) => future.contains(&FlagChange::Set(flag.clone())),
+        Condition::NotFlag(flag) => future.contains(&FlagChange::Clear(flag.clone())),
+        Condition::Counter { counter, at_least, at_most } => {
+            let amounts = future.iter().filter_map(|change| match change {
+                FlagChange::Add { counter: c, amount } if c == counter => Some(*amount),
+                _ => None,
+            });
+            let (gain, loss) = amounts.fold((0, 0), |(gain, loss), amount| {
+                (gain + amount.max(0), loss + amount.min(0))
+            });
+            let value = state.counter(counter);
+            at_least.is_none_or(|min| value + gain >= min) && at_most.is_none_or(|max| value + loss <= max)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
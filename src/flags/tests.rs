@@ -0,0 +1,159 @@
+use super::*;
+
+fn chaos_head() -> GameData {
+    crate::load_title("chaos_head").unwrap()
+}
+
+fn blocked(game: &GameData, playthrough: &str, ending: &str) -> Vec<String> {
+    let playthrough = game.route_index(playthrough).unwrap();
+    let ending = game.route_index(ending).unwrap();
+    game.reachable_endings(playthrough)[ending].blocked_by.iter().map(ToString::to_string).collect()
+}
+
+fn affection_route(required: i32) -> GameData {
+    let effect = |when: &str, amount| Effect {
+        when: Some(when.to_string()),
+        change: FlagChange::Add { counter: "affection".to_string(), amount },
+    };
+
+    GameData::with_routes(vec![Route {
+        name: "Date".to_string(),
+        description: String::new(),
+        prerequisites: vec![],
+        requires: vec![Condition::Counter { counter: "affection".to_string(), at_least: Some(required), at_most: None }],
+        editions: vec![],
+        chapters: vec![Chapter {
+            number: 1,
+            name: "Chapter One".to_string(),
+            steps: vec![Step {
+                id: "invite".to_string(),
+                description: "Invite her out".to_string(),
+                step_type: StepType::Choices { prompts: vec![ChoicePrompt::yes_no("Go out?", true)] },
+                completed: false,
+                optional: false,
+                reload_from: None,
+                effects: vec![effect("YES", 2), effect("NO", -1)],
+                editions: vec![],
+            }],
+        }],
+    }])
+}
+
+#[test]
+fn delusion_triggers_set_flags_and_counters() {
+    let mut game = chaos_head();
+    game.mark_step_completed("daydream_dt1", true);
+    game.mark_step_completed("daydream_dt2", true);
+
+    let state = game.flag_state(game.route_index("Daydream").unwrap());
+    assert_eq!(state.flags.iter().collect::<Vec<_>>(), ["dt1_negative", "dt2_positive"]);
+    assert_eq!(state.counter("negative_delusions"), 1);
+    assert_eq!(state.counter("positive_delusions"), 1);
+    assert_eq!(state.counter("neutral_delusions"), 0);
+    assert_eq!(state.counter(DELUSIONS), 2);
+}
+
+#[test]
+fn neutral_triggers_do_not_count_as_viewed_delusions() {
+    let mut game = chaos_head();
+    game.mark_step_completed("crying_sky_dt1", true);
+    game.mark_step_completed("crying_sky_dt8", true);
+
+    let state = game.flag_state(game.route_index("Crying Sky").unwrap());
+    assert_eq!(state.counter("neutral_delusions"), 1);
+    assert_eq!(state.counter(DELUSIONS), 1);
+}
+
+#[test]
+fn delusion_totals_count_each_remaining_trigger_once() {
+    let mut game = chaos_head();
+    let crying_sky = game.route_index("Crying Sky").unwrap();
+    let viewed = |at_least| Condition::Counter { counter: DELUSIONS.to_string(), at_least: Some(at_least), at_most: None };
+
+    game.routes[crying_sky].requires = vec![viewed(4)];
+    assert!(game.is_ending_reachable(crying_sky));
+
+    game.routes[crying_sky].requires = vec![viewed(5)];
+    assert_eq!(blocked(&game, "Crying Sky", "Crying Sky"), ["delusions ≥ 5"]);
+}
+
+#[test]
+fn a_fresh_playthrough_can_reach_every_ending() {
+    let game = chaos_head();
+    let daydream = game.route_index("Daydream").unwrap();
+    let crying_sky = game.route_index("Crying Sky").unwrap();
+
+    // Crying Sky needs 11 viewed delusions, more than the bundled guide lists
+    // triggers for, so it stays out of reach until the rest are transcribed.
+    for reachability in game.reachable_endings(daydream) {
+        if reachability.route_idx != crying_sky {
+            assert!(reachability.is_reachable(), "{}", game.routes[reachability.route_idx].name);
+        }
+    }
+    assert_eq!(blocked(&game, "Daydream", "Crying Sky"), ["delusions ≥ 11"]);
+}
+
+#[test]
+fn trigger_choices_lock_out_other_endings() {
+    let mut game = chaos_head();
+    game.mark_step_completed("daydream_dt1", true);
+
+    assert!(blocked(&game, "Daydream", "Daydream").is_empty());
+    assert!(blocked(&game, "Daydream", "A Bloody Contract for Your Sins").is_empty());
+    assert_eq!(blocked(&game, "Daydream", "Moon and Sun"), ["dt1_neutral"]);
+}
+
+#[test]
+fn moving_past_a_trigger_without_taking_it_locks_out_the_ending() {
+    let mut game = chaos_head();
+    let daydream = game.route_index("Daydream").unwrap();
+    assert!(game.is_ending_reachable(daydream));
+
+    game.mark_step_completed("daydream_ending", true);
+
+    assert!(!game.is_ending_reachable(daydream));
+    assert_eq!(blocked(&game, "Daydream", "Daydream"), ["dt1_negative", "dt2_positive"]);
+}
+
+#[test]
+fn effects_only_apply_for_the_chosen_option() {
+    let game = affection_route(2);
+    let step = game.step("invite").unwrap();
+
+    assert_eq!(step_changes(step, &["YES".to_string()]), [FlagChange::Add { counter: "affection".to_string(), amount: 2 }]);
+    assert_eq!(step_changes(step, &["NO".to_string()]), [FlagChange::Add { counter: "affection".to_string(), amount: -1 }]);
+}
+
+#[test]
+fn counter_requirements_account_for_what_is_left_to_play() {
+    let mut game = affection_route(2);
+    assert!(game.is_ending_reachable(0));
+
+    game.mark_step_completed("invite", true);
+    assert_eq!(game.flag_state(0).counter("affection"), 2);
+    assert!(game.is_ending_reachable(0));
+
+    let game = affection_route(3);
+    assert_eq!(game.reachable_endings(0)[0].blocked_by.iter().map(ToString::to_string).collect::<Vec<_>>(), ["affection ≥ 3"]);
+}
+
+#[test]
+fn requirements_round_trip_through_json() {
+    let game = affection_route(2);
+    let json = serde_json::to_string(&game.routes).unwrap();
+    let routes: Vec<Route> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(routes[0].requires, game.routes[0].requires);
+    assert_eq!(routes[0].chapters[0].steps[0].effects, game.routes[0].chapters[0].steps[0].effects);
+}
+
+#[test]
+fn recorded_choices_override_the_guide() {
+    let mut game = chaos_head();
+    let daydream = game.route_index("Daydream").unwrap();
+    game.record_choice("daydream_dt1", vec![vec!["Neutral".to_string()]]);
+
+    assert!(game.flag_state(daydream).flags.contains("dt1_neutral"));
+    assert!(!game.is_ending_reachable(daydream));
+    assert!(blocked(&game, "Daydream", "Moon and Sun").is_empty());
+}
@@ -0,0 +1,109 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum GlyphMode {
+    /// Symbols such as ✓, ◐ and 🔒
+    #[default]
+    Unicode,
+    /// Plain ASCII such as [x], [~] and [L]
+    Ascii,
+}
+
+impl GlyphMode {
+    // Without an explicit mode, fall back to ASCII unless the locale says the
+    // terminal speaks UTF-8. LC_ALL overrides LC_CTYPE, which overrides LANG.
+    pub fn resolve(mode: Option<Self>, locale: Option<&str>) -> Self {
+        mode.unwrap_or_else(|| {
+            let utf8 = locale.is_some_and(|locale| {
+                let locale = locale.to_lowercase();
+                locale.contains("utf-8") || locale.contains("utf8")
+            });
+            if utf8 { Self::Unicode } else { Self::Ascii }
+        })
+    }
+
+    pub fn from_env(mode: Option<Self>) -> Self {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty());
+        Self::resolve(mode, locale.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyphs {
+    pub done: &'static str,
+    pub pending: &'static str,
+    pub partial: &'static str,
+    pub locked: &'static str,
+    pub wrong: &'static str,
+    pub skipped: &'static str,
+    pub collapsed: &'static str,
+    pub expanded: &'static str,
+    pub rule: &'static str,
+    pub pointer: &'static str,
+    pub warning: &'static str,
+    pub reload: &'static str,
+    pub note: &'static str,
+    pub cursor: &'static str,
+    pub then: &'static str,
+    pub arrows: &'static str,
+    pub sideways: &'static str,
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Self::new(GlyphMode::Unicode)
+    }
+}
+
+impl Glyphs {
+    pub fn new(mode: GlyphMode) -> Self {
+        match mode {
+            GlyphMode::Unicode => Self {
+                done: "✓",
+                pending: "○",
+                partial: "◐",
+                locked: "🔒",
+                wrong: "✗",
+                skipped: "↷",
+                collapsed: "▸",
+                expanded: "▾",
+                rule: "═══",
+                pointer: "► ",
+                warning: "⚠",
+                reload: "↺",
+                note: "✎",
+                cursor: "▏",
+                then: "→",
+                arrows: "↑/↓",
+                sideways: "←/→",
+            },
+            GlyphMode::Ascii => Self {
+                done: "[x]",
+                pending: "[ ]",
+                partial: "[~]",
+                locked: "[L]",
+                wrong: "[!]",
+                skipped: "[-]",
+                collapsed: "+",
+                expanded: "-",
+                rule: "===",
+                pointer: "> ",
+                warning: "!",
+                reload: "<-",
+                note: "*",
+                cursor: "_",
+                then: "->",
+                arrows: "^/v",
+                sideways: "</>",
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,21 @@
+use super::*;
+
+#[test]
+fn locale_picks_the_glyph_set_unless_overridden() {
+    assert_eq!(GlyphMode::resolve(None, Some("en_GB.UTF-8")), GlyphMode::Unicode);
+    assert_eq!(GlyphMode::resolve(None, Some("ja_JP.utf8")), GlyphMode::Unicode);
+    assert_eq!(GlyphMode::resolve(None, Some("C")), GlyphMode::Ascii);
+    assert_eq!(GlyphMode::resolve(None, None), GlyphMode::Ascii);
+    assert_eq!(GlyphMode::resolve(Some(GlyphMode::Unicode), Some("POSIX")), GlyphMode::Unicode);
+}
+
+#[test]
+fn ascii_glyphs_are_ascii() {
+    let ascii = Glyphs::new(GlyphMode::Ascii);
+    let all = [
+        ascii.done, ascii.pending, ascii.partial, ascii.locked, ascii.wrong, ascii.skipped, ascii.collapsed,
+        ascii.expanded, ascii.rule, ascii.pointer, ascii.warning, ascii.reload, ascii.note, ascii.cursor, ascii.then,
+        ascii.arrows, ascii.sideways,
+    ];
+    assert!(all.iter().all(|glyph| glyph.is_ascii()));
+}
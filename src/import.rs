@@ -0,0 +1,226 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use crate::models::GameData;
+use crate::storage::SavedProgress;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum MergeMode {
+    /// Add what is new, keeping your own value wherever the two disagree
+    #[default]
+    Union,
+    /// Add what is new, taking the imported value wherever the two disagree
+    Overwrite,
+    /// Add what is new, asking which value to keep wherever the two disagree
+    Interactive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Mine,
+    Theirs,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Completed(bool),
+    Skipped,
+    Note(String),
+    SaveSlot(String),
+    Choice(Vec<Vec<String>>),
+    Unlocked,
+    Read,
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Completed(true) => write!(f, "completed"),
+            Value::Completed(false) => write!(f, "not completed"),
+            Value::Skipped => write!(f, "skipped"),
+            Value::Note(note) => write!(f, "note \"{note}\""),
+            Value::SaveSlot(slot) => write!(f, "saved in \"{slot}\""),
+            Value::Choice(choice) => {
+                let prompts: Vec<String> = choice.iter().map(|picked| picked.join(", ")).collect();
+                write!(f, "picked {}", prompts.join("; "))
+            }
+            Value::Unlocked => write!(f, "unlocked"),
+            Value::Read => write!(f, "read"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub step_id: String,
+    pub value: Value,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.step_id, self.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub step_id: String,
+    pub mine: Value,
+    pub theirs: Value,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: yours is {}, the import has {}", self.step_id, self.mine, self.theirs)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Merge {
+    pub progress: SavedProgress,
+    pub changes: Vec<Change>,
+    pub conflicts: Vec<(Conflict, Side)>,
+}
+
+impl Merge {
+    fn take(&mut self, step_id: &str, value: Value, mine: Option<Value>, resolve: &mut impl FnMut(&Conflict) -> Side) -> bool {
+        let side = match mine {
+            Some(mine) if mine == value => return false,
+            Some(mine) => {
+                let conflict = Conflict { step_id: step_id.to_string(), mine, theirs: value.clone() };
+                let side = resolve(&conflict);
+                self.conflicts.push((conflict, side));
+                side
+            }
+            None => Side::Theirs,
+        };
+        if side == Side::Theirs {
+            self.changes.push(Change { step_id: step_id.to_string(), value });
+        }
+        side == Side::Theirs
+    }
+}
+
+// Reads either a JSON export from `sciadv export` or a plain checklist with
+// one completed step id per line. Step ids this title doesn't have are
+// returned rather than imported, since they are most likely typos or an
+// export from another title.
+pub fn parse_import(contents: &str, game: &GameData) -> Result<(SavedProgress, Vec<String>)> {
+    if contents.trim_start().starts_with('{') {
+        let mut saved: SavedProgress = serde_json::from_str(contents).context("not a valid sciadv export")?;
+        let mut unknown = HashSet::new();
+        let mut known = |step_id: &String| {
+            let found = game.step(step_id).is_some();
+            if !found {
+                unknown.insert(step_id.clone());
+            }
+            found
+        };
+        saved.progress.retain(|step_id, _| known(step_id));
+        saved.notes.retain(|step_id, _| known(step_id));
+        saved.save_slots.retain(|step_id, _| known(step_id));
+        saved.choices.retain(|step_id, _| known(step_id));
+        saved.skipped.retain(|step_id| known(step_id));
+
+        let mut unknown: Vec<String> = unknown.into_iter().collect();
+        unknown.sort();
+        return Ok((saved, unknown));
+    }
+
+    let mut saved = SavedProgress::default();
+    let mut unknown = Vec::new();
+    for line in contents.lines() {
+        let step_id = line.trim().trim_start_matches(['-', '*']).trim();
+        if step_id.is_empty() || step_id.starts_with('#') {
+            continue;
+        }
+        if game.step(step_id).is_some() {
+            saved.progress.insert(step_id.to_string(), true);
+        } else {
+            unknown.push(step_id.to_string());
+        }
+    }
+    Ok((saved, unknown))
+}
+
+// Everything the import has that `current` doesn't is added; where both have
+// a different value for the same step, `resolve` picks the side to keep.
+// Having completed a step wins over not having done so without asking.
+pub fn merge(current: &SavedProgress, incoming: SavedProgress, mut resolve: impl FnMut(&Conflict) -> Side) -> Merge {
+    let mut merge = Merge { progress: current.clone(), ..Merge::default() };
+
+    for (step_id, completed) in sorted(incoming.progress) {
+        let mine = merge.progress.progress.get(&step_id).copied().unwrap_or_default();
+        if mine == completed {
+            continue;
+        }
+        let mine = mine.then_some(Value::Completed(true));
+        if merge.take(&step_id, Value::Completed(completed), mine, &mut resolve) {
+            merge.progress.progress.insert(step_id.clone(), completed);
+            if completed {
+                merge.progress.skipped.remove(&step_id);
+            }
+        }
+    }
+
+    let mut skipped: Vec<String> = incoming.skipped.into_iter().collect();
+    skipped.sort();
+    for step_id in skipped {
+        if merge.progress.skipped.contains(&step_id) {
+            continue;
+        }
+        let completed = merge.progress.progress.get(&step_id).copied().unwrap_or_default();
+        let mine = completed.then_some(Value::Completed(true));
+        if merge.take(&step_id, Value::Skipped, mine, &mut resolve) {
+            merge.progress.progress.remove(&step_id);
+            merge.progress.skipped.insert(step_id);
+        }
+    }
+
+    for (step_id, note) in sorted(incoming.notes) {
+        let mine = merge.progress.notes.get(&step_id).cloned().map(Value::Note);
+        if merge.take(&step_id, Value::Note(note.clone()), mine, &mut resolve) {
+            merge.progress.notes.insert(step_id, note);
+        }
+    }
+
+    for (step_id, slot) in sorted(incoming.save_slots) {
+        let mine = merge.progress.save_slots.get(&step_id).cloned().map(Value::SaveSlot);
+        if merge.take(&step_id, Value::SaveSlot(slot.clone()), mine, &mut resolve) {
+            merge.progress.save_slots.insert(step_id, slot);
+        }
+    }
+
+    for (step_id, choice) in sorted(incoming.choices) {
+        let mine = merge.progress.choices.get(&step_id).cloned().map(Value::Choice);
+        if merge.take(&step_id, Value::Choice(choice.clone()), mine, &mut resolve) {
+            merge.progress.choices.insert(step_id, choice);
+        }
+    }
+
+    add_all(&mut merge.progress.achievements, incoming.achievements, Value::Unlocked, &mut merge.changes);
+    add_all(&mut merge.progress.tips, incoming.tips, Value::Read, &mut merge.changes);
+
+    merge
+}
+
+// Sets of earned things can only grow, so there is nothing to conflict on.
+fn add_all(mine: &mut HashSet<String>, incoming: HashSet<String>, value: Value, changes: &mut Vec<Change>) {
+    let mut ids: Vec<String> = incoming.into_iter().collect();
+    ids.sort();
+    for id in ids {
+        if mine.insert(id.clone()) {
+            changes.push(Change { step_id: id, value: value.clone() });
+        }
+    }
+}
+
+fn sorted<V>(map: HashMap<String, V>) -> Vec<(String, V)> {
+    let mut entries: Vec<(String, V)> = map.into_iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,107 @@
+use super::*;
+
+fn chaos_head() -> GameData {
+    crate::load_title("chaos_head").unwrap()
+}
+
+fn saved(progress: &[(&str, bool)], notes: &[(&str, &str)]) -> SavedProgress {
+    SavedProgress {
+        progress: progress.iter().map(|(id, done)| (id.to_string(), *done)).collect(),
+        notes: notes.iter().map(|(id, note)| (id.to_string(), note.to_string())).collect(),
+        ..SavedProgress::default()
+    }
+}
+
+fn messages(changes: &[Change]) -> Vec<String> {
+    changes.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn checklists_list_completed_step_ids() {
+    let checklist = "# Silent Sky\nsilent_sky_ch1_swimsuit\n\n- silent_sky_ch2_hospital\nsilent_sky_typo\n";
+    let (imported, unknown) = parse_import(checklist, &chaos_head()).unwrap();
+
+    assert_eq!(imported, saved(&[("silent_sky_ch1_swimsuit", true), ("silent_sky_ch2_hospital", true)], &[]));
+    assert_eq!(unknown, ["silent_sky_typo"]);
+}
+
+#[test]
+fn exports_are_read_as_json() {
+    let export = r#"{ "progress": { "daydream_dt1": true }, "notes": { "daydream_dt1": "Rooftop" } }"#;
+    let (imported, unknown) = parse_import(export, &chaos_head()).unwrap();
+
+    assert_eq!(imported, saved(&[("daydream_dt1", true)], &[("daydream_dt1", "Rooftop")]));
+    assert!(unknown.is_empty());
+    assert!(parse_import("{ not json", &chaos_head()).is_err());
+}
+
+#[test]
+fn exports_from_other_titles_report_their_unknown_ids() {
+    let export = r#"{
+        "progress": { "daydream_dt1": true, "okabe_ch1_start": true },
+        "notes": { "okabe_ch1_start": "Lab" },
+        "save_slots": { "kurisu_dmail": "Slot 2" },
+        "choices": { "daydream_dt1": [["Negative"]], "phone_trigger": [["Answer"]] },
+        "skipped": ["daydream_dt2", "rine_reply"]
+    }"#;
+    let (imported, unknown) = parse_import(export, &chaos_head()).unwrap();
+
+    assert_eq!(unknown, ["kurisu_dmail", "okabe_ch1_start", "phone_trigger", "rine_reply"]);
+    assert_eq!(imported.progress.keys().collect::<Vec<_>>(), ["daydream_dt1"]);
+    assert!(imported.notes.is_empty());
+    assert!(imported.save_slots.is_empty());
+    assert_eq!(imported.choices.keys().collect::<Vec<_>>(), ["daydream_dt1"]);
+    assert_eq!(imported.skipped.iter().collect::<Vec<_>>(), ["daydream_dt2"]);
+}
+
+#[test]
+fn union_adds_new_progress_and_keeps_yours_on_conflicts() {
+    let mine = saved(&[("a", true), ("b", false)], &[("a", "mine")]);
+    let theirs = saved(&[("a", false), ("b", true), ("c", true)], &[("a", "theirs"), ("c", "new")]);
+    let merge = merge(&mine, theirs, |_| Side::Mine);
+
+    assert_eq!(messages(&merge.changes), ["b: completed", "c: completed", "c: note \"new\""]);
+    assert_eq!(merge.conflicts.len(), 2);
+    assert!(merge.progress.progress["a"]);
+    assert_eq!(merge.progress.notes["a"], "mine");
+}
+
+#[test]
+fn overwrite_takes_the_imported_value_on_conflicts() {
+    let mine = saved(&[("a", true)], &[("a", "mine")]);
+    let theirs = saved(&[("a", false)], &[("a", "theirs")]);
+    let merge = merge(&mine, theirs, |_| Side::Theirs);
+
+    assert_eq!(messages(&merge.changes), ["a: not completed", "a: note \"theirs\""]);
+    assert_eq!(merge.conflicts[0].0.to_string(), "a: yours is completed, the import has not completed");
+    assert_eq!(merge.progress, saved(&[("a", false)], &[("a", "theirs")]));
+}
+
+#[test]
+fn skipping_a_completed_step_is_a_conflict() {
+    let mine = saved(&[("a", true)], &[]);
+    let mut theirs = SavedProgress::default();
+    theirs.skipped.extend(["a".to_string(), "b".to_string()]);
+
+    let kept = merge(&mine, theirs.clone(), |_| Side::Mine);
+    assert_eq!(messages(&kept.changes), ["b: skipped"]);
+    assert!(kept.progress.progress["a"]);
+
+    let taken = merge(&mine, theirs, |_| Side::Theirs);
+    assert!(!taken.progress.progress.contains_key("a"));
+    assert!(taken.progress.skipped.contains("a"));
+}
+
+#[test]
+fn unlocked_achievements_and_read_tips_are_added() {
+    let mut mine = SavedProgress::default();
+    mine.achievements.insert("known".to_string());
+    let mut theirs = SavedProgress::default();
+    theirs.achievements.extend(["known".to_string(), "new".to_string()]);
+    theirs.tips.insert("tip".to_string());
+    let merge = merge(&mine, theirs, |_| Side::Mine);
+
+    assert_eq!(messages(&merge.changes), ["new: unlocked", "tip: read"]);
+    assert!(merge.conflicts.is_empty());
+    assert_eq!(merge.progress.achievements.len(), 2);
+}
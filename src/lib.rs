@@ -0,0 +1,76 @@
+//! Route guides and progress tracking for the Science Adventure series.
+//!
+//! The `sciadv` binary is a thin layer over this crate: it resolves a title with
+//! [`load_title`] and hands the resulting [`GameData`] to [`tui::run_app`].
+//!
+//! ```
+//! let mut game = sciadv::load_title("chaos_head").unwrap();
+//!
+//! game.mark_step_completed("silent_sky_ch1_swimsuit", true);
+//! let silent_sky = game.route_index("Silent Sky").unwrap();
+//! assert!(game.get_completion_percentage(silent_sky) > 0.0);
+//! ```
+
+pub mod config;
+pub mod data;
+pub mod flags;
+pub mod glyphs;
+pub mod import;
+pub mod lint;
+pub mod models;
+pub mod server;
+pub mod storage;
+pub mod theme;
+pub mod titles;
+pub mod tui;
+
+#[cfg(test)]
+mod testing;
+
+use std::collections::HashMap;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+
+pub use models::{Achievement, Chapter, ChoicePrompt, CollectionItem, CollectionKind, Edition, GameData, Pick, Route, SpoilerLevel, Step, StepType, Tip, Unlock};
+
+pub static VALID_TITLES: Lazy<HashMap<&'static str, Option<&'static str>>> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    m.insert("chaos_head", Some("Chaos;Head"));
+
+    m.insert("steins_gate", None);
+    m.insert("robotics_notes", None);
+    m.insert("chaos_child", None);
+    m.insert("occultic_nine", None);
+    m.insert("anonymous_code", None);
+
+    m
+});
+
+pub fn load_title(title: &str) -> Option<GameData> {
+    load_edition(title, None)
+}
+
+// The bundled data plus the player's extras file, which is what the TUI,
+// `serve` and the other commands all show.
+pub fn open_edition(title: &str, edition: Option<&str>) -> Result<Option<GameData>> {
+    let Some(game) = load_edition(title, edition) else {
+        return Ok(None);
+    };
+    let extras = data::Extras::load(&data::Extras::path(title)?)?;
+    Ok(Some(game.with_achievements(extras.achievements).with_tips(extras.tips)))
+}
+
+pub fn load_edition(title: &str, edition: Option<&str>) -> Option<GameData> {
+    let editions = data::editions(title);
+    let edition = match edition {
+        Some(id) => editions.iter().find(|edition| edition.id == id)?,
+        None => editions.first()?,
+    };
+    data::load_title(title)
+        .map(|routes| {
+            GameData::for_edition(routes, *edition)
+                .with_achievements(data::achievements(title))
+                .with_tips(data::tips(title))
+                .with_collection(data::collection(title))
+        })
+}
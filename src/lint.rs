@@ -0,0 +1,322 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use crate::flags;
+use crate::models::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub severity: Severity,
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}: {}: {}", self.location, self.message)
+    }
+}
+
+pub fn lint_routes(source: &str, routes: &[Route]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let route_names: HashSet<&str> = routes.iter().map(|r| r.name.as_str()).collect();
+    let mut seen_routes: HashSet<&str> = HashSet::new();
+    let mut seen_steps: HashMap<&str, String> = HashMap::new();
+    let step_types: HashMap<&str, &StepType> = routes.iter()
+        .flat_map(|route| &route.chapters)
+        .flat_map(|chapter| &chapter.steps)
+        .map(|step| (step.id.as_str(), &step.step_type))
+        .collect();
+    let changes: Vec<FlagChange> = routes.iter()
+        .flat_map(|route| &route.chapters)
+        .flat_map(|chapter| &chapter.steps)
+        .flat_map(flags::possible_changes)
+        .collect();
+
+    for route in routes {
+        let route_location = format!("{source} > {}", route.name);
+        let mut push = |severity, location: &str, message: String| {
+            issues.push(Issue { severity, location: location.to_string(), message });
+        };
+
+        if !seen_routes.insert(&route.name) {
+            push(Severity::Error, &route_location, "duplicate route name".to_string());
+        }
+
+        for prereq in &route.prerequisites {
+            if *prereq == route.name {
+                push(Severity::Error, &route_location, "route lists itself as a prerequisite".to_string());
+            } else if !route_names.contains(prereq.as_str()) {
+                push(Severity::Error, &route_location, format!("prerequisite \"{prereq}\" is not a route in this guide"));
+            }
+        }
+
+        for condition in &route.requires {
+            let settable = match condition {
+                Condition::Flag(flag) | Condition::NotFlag(flag) => changes.iter()
+                    .any(|change| matches!(change, FlagChange::Set(f) if f == flag)),
+                Condition::Counter { counter, .. } => changes.iter()
+                    .any(|change| matches!(change, FlagChange::Add { counter: c, .. } if c == counter)),
+            };
+            if !settable {
+                push(Severity::Warning, &route_location, format!("requirement \"{condition}\" uses a flag no step sets"));
+            }
+        }
+
+        if route.chapters.is_empty() {
+            push(Severity::Warning, &route_location, "route has no chapters".to_string());
+        }
+
+        for pair in route.chapters.windows(2) {
+            if pair[1].number <= pair[0].number {
+                push(
+                    Severity::Error,
+                    &format!("{route_location} > {}", pair[1].name),
+                    format!("chapter number {} does not follow chapter number {}", pair[1].number, pair[0].number),
+                );
+            }
+        }
+
+        for chapter in &route.chapters {
+            let chapter_location = format!("{route_location} > {}", chapter.name);
+
+            if chapter.steps.is_empty() {
+                push(Severity::Warning, &chapter_location, "chapter has no steps".to_string());
+            }
+
+            for step in &chapter.steps {
+                let step_location = format!("{chapter_location} > {}", step.id);
+
+                if step.id.trim().is_empty() {
+                    push(Severity::Error, &step_location, "step id is empty".to_string());
+                } else if let Some(first) = seen_steps.get(step.id.as_str()) {
+                    push(Severity::Error, &step_location, format!("duplicate step id, first used at {first}"));
+                } else {
+                    seen_steps.insert(&step.id, step_location.clone());
+                }
+
+                if !step.optional && step.description.to_lowercase().starts_with("optional") {
+                    push(Severity::Warning, &step_location, "description says optional but the step is not marked optional".to_string());
+                }
+
+                if let Some(checkpoint) = &step.reload_from {
+                    match step_types.get(checkpoint.as_str()) {
+                        Some(StepType::Checkpoint { .. }) => {}
+                        Some(_) => push(
+                            Severity::Error,
+                            &step_location,
+                            format!("reload_from \"{checkpoint}\" is not a checkpoint step"),
+                        ),
+                        None => push(
+                            Severity::Error,
+                            &step_location,
+                            format!("reload_from \"{checkpoint}\" is not a step in this guide"),
+                        ),
+                    }
+                }
+
+                if !route.editions.is_empty() {
+                    for edition in step.editions.iter().filter(|e| !route.editions.contains(e)) {
+                        push(Severity::Warning, &step_location, format!("step is limited to edition \"{edition}\", which its route is not in"));
+                    }
+                }
+
+                for when in step.effects.iter().filter_map(|effect| effect.when.as_deref()) {
+                    let known = match &step.step_type {
+                        StepType::DelusionTrigger { .. } => Polarity::ALL.iter().any(|p| p.name() == when),
+                        StepType::Choices { prompts } => {
+                            prompts.iter().any(|prompt| prompt.options.iter().any(|o| o == when))
+                        }
+                        StepType::GeneralInstruction { .. } | StepType::Checkpoint { .. } => false,
+                    };
+                    if !known {
+                        push(Severity::Error, &step_location, format!("effect depends on \"{when}\", which this step never offers"));
+                    }
+                }
+
+                if let StepType::Choices { prompts } = &step.step_type {
+                    if prompts.is_empty() {
+                        push(Severity::Warning, &step_location, "choice step has no prompts".to_string());
+                    }
+
+                    for (i, prompt) in prompts.iter().enumerate() {
+                        let prompt_location = format!("{step_location} > prompt {}", i + 1);
+
+                        if is_placeholder_question(&prompt.question) {
+                            push(
+                                Severity::Warning,
+                                &prompt_location,
+                                format!("question \"{}\" looks like a placeholder, use the in-game text", prompt.question),
+                            );
+                        }
+                        if prompt.options.is_empty() {
+                            push(Severity::Error, &prompt_location, "prompt has no options".to_string());
+                        }
+                        let unique: HashSet<&String> = prompt.options.iter().collect();
+                        if unique.len() != prompt.options.len() {
+                            push(Severity::Warning, &prompt_location, "prompt lists the same option twice".to_string());
+                        }
+                        for option in prompt.pick.options() {
+                            if !prompt.options.contains(option) {
+                                push(Severity::Error, &prompt_location, format!("pick \"{option}\" is not one of the options"));
+                            }
+                        }
+                    }
+                }
+
+                if let StepType::DelusionTrigger { trigger_number, .. } = &step.step_type {
+                    match description_trigger_number(&step.description) {
+                        Some(n) if n == *trigger_number => {}
+                        Some(n) => push(
+                            Severity::Error,
+                            &step_location,
+                            format!("description says trigger #{n} but trigger_number is {trigger_number}"),
+                        ),
+                        None => push(
+                            Severity::Warning,
+                            &step_location,
+                            format!("description does not mention trigger #{trigger_number}"),
+                        ),
+                    }
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+pub fn lint_achievements(source: &str, routes: &[Route], achievements: &[Achievement]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+
+    for achievement in achievements {
+        let location = format!("{source} > achievements > {}", achievement.id);
+        let mut push = |severity, message: String| {
+            issues.push(Issue { severity, location: location.clone(), message });
+        };
+
+        if achievement.id.trim().is_empty() {
+            push(Severity::Error, "achievement id is empty".to_string());
+        } else if !seen.insert(&achievement.id) {
+            push(Severity::Error, "duplicate achievement id".to_string());
+        }
+        if achievement.name.trim().is_empty() {
+            push(Severity::Error, "achievement has no name".to_string());
+        }
+        if let Some(message) = achievement.unlock.as_ref().and_then(|unlock| unlock_problem(routes, unlock)) {
+            push(Severity::Error, message);
+        }
+    }
+
+    issues
+}
+
+pub fn lint_tips(source: &str, routes: &[Route], tips: &[Tip]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+
+    for tip in tips {
+        let location = format!("{source} > tips > {}", tip.id);
+        let mut push = |severity, message: String| {
+            issues.push(Issue { severity, location: location.clone(), message });
+        };
+
+        if tip.id.trim().is_empty() {
+            push(Severity::Error, "tip id is empty".to_string());
+        } else if !seen.insert(&tip.id) {
+            push(Severity::Error, "duplicate tip id".to_string());
+        }
+        if tip.term.trim().is_empty() {
+            push(Severity::Error, "tip has no term".to_string());
+        }
+        for name in tip.routes.iter().filter(|name| !routes.iter().any(|route| route.name == **name)) {
+            push(Severity::Error, format!("limited to route \"{name}\", which is not a route in this guide"));
+        }
+
+        let has_chapter = routes.iter()
+            .filter(|route| tip.routes.is_empty() || tip.routes.contains(&route.name))
+            .flat_map(|route| &route.chapters)
+            .any(|chapter| chapter.number == tip.chapter);
+        if !has_chapter {
+            push(Severity::Warning, format!("chapter {} is not in any of its routes", tip.chapter));
+        }
+    }
+
+    issues
+}
+
+pub fn lint_collection(source: &str, routes: &[Route], collection: &[CollectionItem]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+
+    for item in collection {
+        let location = format!("{source} > collection > {}", item.id);
+        let mut push = |severity, message: String| {
+            issues.push(Issue { severity, location: location.clone(), message });
+        };
+
+        if item.id.trim().is_empty() {
+            push(Severity::Error, "collection item id is empty".to_string());
+        } else if !seen.insert(&item.id) {
+            push(Severity::Error, "duplicate collection item id".to_string());
+        }
+        if item.name.trim().is_empty() {
+            push(Severity::Error, "collection item has no name".to_string());
+        }
+        if let Some(message) = unlock_problem(routes, &item.unlock) {
+            push(Severity::Error, message);
+        }
+    }
+
+    issues
+}
+
+fn unlock_problem(routes: &[Route], unlock: &Unlock) -> Option<String> {
+    match unlock {
+        Unlock::Step(step_id) => {
+            let known = routes.iter()
+                .flat_map(|route| &route.chapters)
+                .flat_map(|chapter| &chapter.steps)
+                .any(|step| step.id == *step_id);
+            (!known).then(|| format!("unlocked by step \"{step_id}\", which is not a step in this guide"))
+        }
+        Unlock::Route(name) => {
+            let known = routes.iter().any(|route| route.name == *name);
+            (!known).then(|| format!("unlocked by route \"{name}\", which is not a route in this guide"))
+        }
+    }
+}
+
+fn is_placeholder_question(question: &str) -> bool {
+    const ORDINALS: [&str; 10] = [
+        "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth",
+    ];
+
+    let question = question.trim().to_lowercase();
+    let numbered = question.strip_prefix("question")
+        .map(str::trim)
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    let ordinal = question.ends_with(" question")
+        && ORDINALS.iter().any(|ordinal| question.starts_with(ordinal));
+
+    question.is_empty() || numbered || ordinal || ["todo", "tbd", "placeholder"].iter().any(|m| question.contains(m))
+}
+
+fn description_trigger_number(description: &str) -> Option<u32> {
+    let (_, rest) = description.split_once('#')?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests;
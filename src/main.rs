@@ -1,53 +1,509 @@
-mod models;
-mod data;
-mod tui;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::engine::{ArgValueCompleter, CompletionCandidate};
+use clap_complete::env::Shells;
+use clap_complete::{CompleteEnv, Shell};
+use std::ffi::OsStr;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context, Result};
+use sciadv::config::Config;
+use sciadv::data::Extras;
+use sciadv::glyphs::{GlyphMode, Glyphs};
+use sciadv::import::{self, Conflict, MergeMode, Side};
+use sciadv::lint::{self, Severity};
+use sciadv::server::GuideServer;
+use sciadv::storage::{ProgressStore, SavedProgress};
+use sciadv::theme::ThemeName;
+use sciadv::titles::{self, TitleLookup};
+use sciadv::tui::Keymap;
+use sciadv::{data, tui, GameData, SpoilerLevel, VALID_TITLES};
 
-use clap::Parser;
-use std::collections::HashMap;
-use once_cell::sync::Lazy;
-use anyhow::Result;
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[arg(short, long, global = true, add = ArgValueCompleter::new(complete_title))]
+    title: Option<String>,
 
-pub static VALID_TITLES: Lazy<HashMap<&'static str, Option<&'static str>>> = Lazy::new(|| {
-    let mut m = HashMap::new();
-    m.insert("chaos_head", Some("Chaos;Head"));
+    /// Edition of the title, e.g. noah or original for chaos_head
+    #[arg(short, long, global = true)]
+    edition: Option<String>,
 
-    m.insert("steins_gate", None);
-    m.insert("robotics_notes", None);
-    m.insert("chaos_child", None);
-    m.insert("occultic_nine", None);
-    m.insert("anonymous_code", None);
+    #[arg(short, long, value_enum)]
+    spoilers: Option<SpoilerLevel>,
 
-    m
-});
+    /// Colour theme; defaults to dark, or no-color when NO_COLOR is set
+    #[arg(long, value_enum)]
+    theme: Option<ThemeName>,
 
-#[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
-struct Args {
-    #[arg(short, long)]
-    title: String,
+    /// Status symbols; defaults to unicode on UTF-8 locales and ascii otherwise
+    #[arg(long, value_enum)]
+    glyphs: Option<GlyphMode>,
+
+    #[arg(long, value_enum)]
+    keymap: Option<Keymap>,
+
+    /// Start with fully completed chapters collapsed
+    #[arg(long)]
+    collapse_completed: bool,
+
+    /// Config file to read instead of $XDG_CONFIG_HOME/sciadv/config.toml
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check bundled titles, or the given guide files, for data errors
+    Lint {
+        guides: Vec<PathBuf>,
+    },
+    /// Serve the title's guide and progress over HTTP
+    Serve {
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        #[arg(short, long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+        bind: IpAddr,
+    },
+    /// List step notes, optionally only those matching a search term
+    Notes {
+        #[arg(add = ArgValueCompleter::new(complete_step_id))]
+        query: Option<String>,
+    },
+    /// Show which endings the playthrough following a route can still reach
+    Endings {
+        #[arg(add = ArgValueCompleter::new(complete_route))]
+        route: String,
+    },
+    /// Print the effective configuration, after command line overrides
+    Config,
+    /// Print a script that sets up tab completion, including title and step ids
+    Completions {
+        shell: Shell,
+    },
+    /// Print the man page in roff format
+    Man,
+    /// Export progress and notes as JSON
+    Export {
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import progress from an export or a checklist of step ids, one per line
+    Import {
+        file: PathBuf,
+
+        #[arg(short, long, value_enum, default_value_t = MergeMode::Union)]
+        mode: MergeMode,
+
+        /// Show what would change without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+impl Args {
+    fn overrides(&self) -> Config {
+        Config {
+            title: self.title.clone(),
+            edition: self.edition.clone(),
+            spoilers: self.spoilers,
+            theme: self.theme,
+            glyphs: self.glyphs,
+            keymap: self.keymap,
+            collapse_completed: self.collapse_completed.then_some(true),
+        }
+    }
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    CompleteEnv::with_factory(Args::command).var(COMPLETE_VAR).complete();
 
-    let query = args.title.as_str();
+    let args = Args::parse();
+    let config_path = match &args.config {
+        Some(path) => path.clone(),
+        None => Config::default_path()?,
+    };
+    let config = args.overrides().or(Config::load(&config_path)?).resolved();
 
-    match VALID_TITLES.get(query) {
-        Some(Some(stylized)) => {
-            if query == "chaos_head" {
-                tui::run_app()?;
+    match &args.command {
+        Some(Command::Lint { guides }) => run_lint(guides),
+        Some(Command::Serve { port, bind }) => {
+            if let Some((title, game_data)) = resolve_title(require_title(&config), config.edition.as_deref())?
+                && let Some(edition) = game_data.edition
+            {
+                GuideServer::new(title, edition.id, ProgressStore::for_title(title, edition.id)?)
+                    .run(SocketAddr::new(*bind, *port))?;
+            }
+            Ok(())
+        }
+        Some(Command::Notes { query }) => {
+            if let Some((game_data, _)) = open_title(require_title(&config), config.edition.as_deref())? {
+                print_notes(&game_data, query.as_deref().unwrap_or_default());
+            }
+            Ok(())
+        }
+        Some(Command::Endings { route }) => {
+            if let Some((game_data, _)) = open_title(require_title(&config), config.edition.as_deref())? {
+                print_endings(&game_data, route, Glyphs::new(config.preferences().glyphs))?;
+            }
+            Ok(())
+        }
+        Some(Command::Config) => {
+            if config_path.exists() {
+                println!("# {}", config_path.display());
             } else {
-                println!("\"{stylized}\" is known but not yet implemented");
+                println!("# {} (not found, using defaults)", config_path.display());
             }
+            print!("{}", canonical_title(config).to_toml()?);
+            Ok(())
+        }
+        Some(Command::Completions { shell }) => {
+            let shells = Shells::builtins();
+            let Some(completer) = shells.completer(&shell.to_string()) else {
+                bail!("completions are not supported for {shell}");
+            };
+            completer.write_registration(COMPLETE_VAR, "sciadv", "sciadv", "sciadv", &mut io::stdout())?;
+            Ok(())
         }
-        Some(None) => {
-            println!("\"{query}\" is known but not yet implemented");
+        Some(Command::Man) => {
+            clap_mangen::Man::new(Args::command()).render(&mut io::stdout())?;
+            Ok(())
+        }
+        Some(Command::Import { file, mode, dry_run }) => {
+            if let Some((game_data, store)) = open_title(require_title(&config), config.edition.as_deref())? {
+                run_import(&game_data, &store, file, *mode, *dry_run)?;
+            }
+            Ok(())
+        }
+        Some(Command::Export { output }) => {
+            if let Some((game_data, _)) = open_title(require_title(&config), config.edition.as_deref())? {
+                let json = serde_json::to_string_pretty(&SavedProgress::from_game(&game_data))?;
+                match output {
+                    Some(path) => fs::write(path, json + "\n")
+                        .with_context(|| format!("failed to write {}", path.display()))?,
+                    None => println!("{json}"),
+                }
+            }
+            Ok(())
         }
         None => {
+            if let Some((game_data, store)) = open_title(require_title(&config), config.edition.as_deref())? {
+                tui::run_app(game_data, store, config.preferences())?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn require_title(config: &Config) -> &str {
+    match config.title.as_deref() {
+        Some(title) => title,
+        None => Args::command()
+            .error(ErrorKind::MissingRequiredArgument, "--title is required, or set title in the config file")
+            .exit(),
+    }
+}
+
+fn open_title(query: &str, edition: Option<&str>) -> Result<Option<(GameData, ProgressStore)>> {
+    let Some((title, mut game_data)) = resolve_title(query, edition)? else {
+        return Ok(None);
+    };
+    let Some(edition) = game_data.edition else {
+        bail!("\"{title}\" has no editions");
+    };
+    let store = ProgressStore::for_title(title, edition.id)?;
+    store.load(&mut game_data)?;
+    Ok(Some((game_data, store)))
+}
+
+fn print_notes(game_data: &GameData, query: &str) {
+    let matches = game_data.search_notes(query);
+    if matches.is_empty() {
+        println!("No notes match \"{query}\"");
+    }
+
+    for (step_id, note) in matches {
+        match game_data.step(step_id) {
+            Some(step) => println!("{step_id} ({}):\n  {note}", step.description),
+            None => println!("{step_id}:\n  {note}"),
+        }
+    }
+}
+
+fn print_endings(game_data: &GameData, route: &str, glyphs: Glyphs) -> Result<()> {
+    let Some(route_idx) = game_data.route_index(route) else {
+        bail!("\"{route}\" is not a route in this title");
+    };
+
+    for ending in game_data.reachable_endings(route_idx) {
+        let name = &game_data.routes[ending.route_idx].name;
+        if ending.is_reachable() {
+            println!("{} {name}", glyphs.done);
+        } else {
+            let needs: Vec<String> = ending.blocked_by.iter().map(ToString::to_string).collect();
+            println!("{} {name} (needs {})", glyphs.wrong, needs.join(", "));
+        }
+    }
+    Ok(())
+}
+
+// Spells an alias like "noah" out as the title and edition it stands for.
+fn canonical_title(mut config: Config) -> Config {
+    if let Some(query) = &config.title
+        && let TitleLookup::Found { title, edition } = titles::find_title(query)
+    {
+        config.title = Some(title.to_string());
+        config.edition = config.edition.or(edition.map(str::to_string));
+    }
+    config
+}
+
+// Looks the title up by id, alias or prefix, returning its id alongside the
+// data. An edition named on the command line wins over one implied by an alias.
+fn resolve_title(query: &str, edition: Option<&str>) -> Result<Option<(&'static str, GameData)>> {
+    let (title, edition) = match titles::find_title(query) {
+        TitleLookup::Found { title, edition: implied } => (title, edition.or(implied)),
+        TitleLookup::Ambiguous(candidates) => {
+            println!("\"{query}\" could be any of: {}", candidates.join(", "));
+            return Ok(None);
+        }
+        TitleLookup::NotFound { suggestion } => {
             println!("\"{query}\" is not a recognised title");
-            println!("Available titles: chaos_head");
+            if let Some(suggestion) = suggestion {
+                println!("Did you mean \"{suggestion}\"?");
+            }
+            println!("Available titles: {}", titles::available_titles().join(", "));
+            return Ok(None);
+        }
+    };
+
+    let stylized = VALID_TITLES.get(title).copied().flatten().unwrap_or(title);
+    let editions = data::editions(title);
+    if let Some(edition) = edition
+        && !editions.iter().any(|e| e.id == edition)
+    {
+        println!("\"{edition}\" is not an edition of {stylized}");
+        let ids: Vec<&str> = editions.iter().map(|e| e.id).collect();
+        println!("Available editions: {}", ids.join(", "));
+        return Ok(None);
+    }
+
+    match sciadv::open_edition(title, edition)? {
+        Some(game_data) => Ok(Some((title, game_data))),
+        None => {
+            println!("\"{stylized}\" is known but not yet implemented");
+            Ok(None)
         }
     }
+}
+
+fn run_import(game_data: &GameData, store: &ProgressStore, file: &Path, mode: MergeMode, dry_run: bool) -> Result<()> {
+    let contents = fs::read_to_string(file).with_context(|| format!("failed to read {}", file.display()))?;
+    let (incoming, unknown) = import::parse_import(&contents, game_data)
+        .with_context(|| format!("failed to import {}", file.display()))?;
+    for step_id in &unknown {
+        println!("Skipping \"{step_id}\", which is not a step in this title");
+    }
+
+    let merge = import::merge(&SavedProgress::from_game(game_data), incoming, |conflict| match mode {
+        MergeMode::Union => Side::Mine,
+        MergeMode::Overwrite => Side::Theirs,
+        MergeMode::Interactive => ask_conflict(conflict),
+    });
+
+    let verb = if dry_run { "Would import" } else { "Importing" };
+    for change in &merge.changes {
+        println!("{verb} {change}");
+    }
+    let kept = merge.conflicts.iter().filter(|(_, side)| *side == Side::Mine).count();
+    println!("{} change(s), {kept} conflict(s) kept as they were", merge.changes.len());
 
+    if !dry_run && !merge.changes.is_empty() {
+        store.save_progress(&merge.progress)?;
+        println!("Saved to {}", store.path().display());
+    }
     Ok(())
 }
+
+fn ask_conflict(conflict: &Conflict) -> Side {
+    print!("{conflict}. Take the imported value? [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    let _ = io::stdin().lock().read_line(&mut answer);
+    if answer.trim().eq_ignore_ascii_case("y") { Side::Theirs } else { Side::Mine }
+}
+
+// The shell calls back into `sciadv` with this variable set to get
+// completions, so candidates can depend on the installed titles' data.
+const COMPLETE_VAR: &str = "COMPLETE";
+
+fn complete_title(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    let mut titles: Vec<(&str, Option<&str>)> = VALID_TITLES.iter()
+        .filter(|(title, _)| title.starts_with(current.as_ref()))
+        .map(|(title, stylized)| (*title, *stylized))
+        .collect();
+    titles.sort();
+    titles.into_iter()
+        .map(|(title, stylized)| CompletionCandidate::new(title).help(stylized.map(Into::into)))
+        .collect()
+}
+
+fn complete_step_id(current: &OsStr) -> Vec<CompletionCandidate> {
+    step_candidates(completing_title().as_deref(), &current.to_string_lossy())
+}
+
+fn complete_route(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    completing_routes(completing_title().as_deref())
+        .iter()
+        .filter(|route| route.name.starts_with(current.as_ref()))
+        .map(|route| CompletionCandidate::new(&route.name))
+        .collect()
+}
+
+fn step_candidates(title: Option<&str>, current: &str) -> Vec<CompletionCandidate> {
+    completing_routes(title)
+        .iter()
+        .flat_map(|route| &route.chapters)
+        .flat_map(|chapter| &chapter.steps)
+        .filter(|step| step.id.starts_with(current))
+        .map(|step| CompletionCandidate::new(&step.id).help(Some(step.description.clone().into())))
+        .collect()
+}
+
+fn completing_routes(title: Option<&str>) -> Vec<sciadv::Route> {
+    match title.map(titles::find_title) {
+        Some(TitleLookup::Found { title, .. }) => data::load_title(title).unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+// While completing, the words typed so far follow `--` in our own arguments.
+// Falls back to the title in the config file when none was typed.
+fn completing_title() -> Option<String> {
+    let words: Vec<String> = std::env::args().skip_while(|arg| arg != "--").skip(1).collect();
+    title_from_words(&words).or_else(|| {
+        let path = flag_value(&words, "--config").map(PathBuf::from).or_else(|| Config::default_path().ok())?;
+        Config::load(&path).ok()?.title
+    })
+}
+
+fn title_from_words(words: &[String]) -> Option<String> {
+    flag_value(words, "-t").or_else(|| flag_value(words, "--title"))
+}
+
+fn flag_value(words: &[String], flag: &str) -> Option<String> {
+    words.iter().enumerate().rev().find_map(|(i, word)| {
+        if word == flag {
+            words.get(i + 1).cloned()
+        } else {
+            word.strip_prefix(flag)?.strip_prefix('=').map(str::to_string)
+        }
+    })
+}
+
+fn run_lint(guides: &[PathBuf]) -> Result<()> {
+    let mut issues = Vec::new();
+
+    if guides.is_empty() {
+        let mut titles: Vec<&str> = VALID_TITLES.keys().copied().collect();
+        titles.sort();
+        for title in titles {
+            if let Some(routes) = data::load_title(title) {
+                issues.extend(lint::lint_routes(title, &routes));
+                issues.extend(lint::lint_achievements(title, &routes, &data::achievements(title)));
+                issues.extend(lint::lint_tips(title, &routes, &data::tips(title)));
+                issues.extend(lint::lint_collection(title, &routes, &data::collection(title)));
+
+                let extras_path = Extras::path(title)?;
+                if extras_path.exists() {
+                    let source = extras_path.display().to_string();
+                    let extras = Extras::load(&extras_path)?;
+                    issues.extend(lint::lint_achievements(&source, &routes, &extras.achievements));
+                    issues.extend(lint::lint_tips(&source, &routes, &extras.tips));
+                }
+            }
+        }
+    } else {
+        for path in guides {
+            let routes = data::load_guide_file(path)?;
+            issues.extend(lint::lint_routes(&path.display().to_string(), &routes));
+        }
+    }
+
+    for issue in &issues {
+        println!("{issue}");
+    }
+
+    let errors = issues.iter().filter(|i| i.severity == Severity::Error).count();
+    let warnings = issues.len() - errors;
+    println!("{errors} error(s), {warnings} warning(s)");
+
+    if errors > 0 {
+        bail!("lint found {errors} error(s)");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(candidates: Vec<CompletionCandidate>) -> Vec<String> {
+        candidates.iter().map(|candidate| candidate.get_value().to_string_lossy().into_owned()).collect()
+    }
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Args::command().debug_assert();
+    }
+
+    #[test]
+    fn titles_complete_from_the_known_ids() {
+        assert_eq!(values(complete_title(OsStr::new("chaos"))), ["chaos_child", "chaos_head"]);
+        assert_eq!(values(complete_title(OsStr::new("steins"))), ["steins_gate"]);
+    }
+
+    #[test]
+    fn step_ids_complete_from_the_typed_title() {
+        let words: Vec<String> = ["sciadv", "--title=Chaos;Head", "notes", "silent_sky_ch6"].map(String::from).into();
+        let title = title_from_words(&words);
+        assert_eq!(title.as_deref(), Some("Chaos;Head"));
+        assert_eq!(
+            values(step_candidates(title.as_deref(), "silent_sky_ch6")),
+            ["silent_sky_ch6_save", "silent_sky_ch6_bad_ending", "silent_sky_ch6_proceed"],
+        );
+        assert!(step_candidates(Some("steins_gate"), "").is_empty());
+        assert!(step_candidates(None, "").is_empty());
+    }
+
+    #[test]
+    fn config_shows_aliases_as_the_title_and_edition() {
+        let alias = Config { title: Some("noah".to_string()), ..Config::default() };
+        let config = canonical_title(alias);
+        assert_eq!(config.title.as_deref(), Some("chaos_head"));
+        assert_eq!(config.edition.as_deref(), Some("noah"));
+
+        let explicit = Config { title: Some("noah".to_string()), edition: Some("original".to_string()), ..Config::default() };
+        assert_eq!(canonical_title(explicit).edition.as_deref(), Some("original"));
+
+        let unknown = Config { title: Some("nonexistent".to_string()), ..Config::default() };
+        assert_eq!(canonical_title(unknown.clone()), unknown);
+    }
+
+    #[test]
+    fn man_page_covers_the_subcommands() {
+        let mut page = Vec::new();
+        clap_mangen::Man::new(Args::command()).render(&mut page).unwrap();
+        let page = String::from_utf8(page).unwrap();
+        assert!(page.contains("completions"));
+        assert!(page.contains("config"));
+    }
+}
@@ -1,5 +1,7 @@
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Route {
@@ -7,6 +9,10 @@ pub struct Route {
     pub description: String,
     pub chapters: Vec<Chapter>,
     pub prerequisites: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<Condition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub editions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +28,14 @@ pub struct Step {
     pub description: String,
     pub step_type: StepType,
     pub completed: bool,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reload_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub effects: Vec<Effect>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub editions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,8 +45,9 @@ pub enum StepType {
         polarity: Polarity,
         location: String,
     },
-    YesNoPrompts {
-        prompts: Vec<PromptChoice>,
+    #[serde(alias = "YesNoPrompts")]
+    Choices {
+        prompts: Vec<ChoicePrompt>,
     },
     GeneralInstruction {
         instruction: String,
@@ -49,56 +64,651 @@ pub enum Polarity {
     Neutral,
 }
 
+impl Polarity {
+    pub const ALL: [Polarity; 3] = [Polarity::Positive, Polarity::Negative, Polarity::Neutral];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Polarity::Positive => "Positive",
+            Polarity::Negative => "Negative",
+            Polarity::Neutral => "Neutral",
+        }
+    }
+}
+
+impl Step {
+    // The options a player can pick at this step, one list per prompt.
+    pub fn choice_options(&self) -> Vec<Vec<String>> {
+        match &self.step_type {
+            StepType::DelusionTrigger { .. } => {
+                vec![Polarity::ALL.iter().map(|p| p.name().to_string()).collect()]
+            }
+            StepType::Choices { prompts } => {
+                prompts.iter().map(|prompt| prompt.options.clone()).collect()
+            }
+            StepType::GeneralInstruction { .. } | StepType::Checkpoint { .. } => vec![],
+        }
+    }
+
+    pub fn accepts(&self, choice: &[Vec<String>]) -> bool {
+        match &self.step_type {
+            StepType::DelusionTrigger { polarity, .. } => choice == [vec![polarity.name().to_string()]],
+            StepType::Choices { prompts } => {
+                choice.len() == prompts.len()
+                    && prompts.iter().zip(choice).all(|(prompt, picked)| prompt.pick.accepts(picked))
+            }
+            StepType::GeneralInstruction { .. } | StepType::Checkpoint { .. } => true,
+        }
+    }
+}
+
+// What completing a step does to the game's hidden state. `when` limits the
+// effect to playthroughs where that option was chosen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Effect {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    pub change: FlagChange,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlagChange {
+    Set(String),
+    Clear(String),
+    Add { counter: String, amount: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Condition {
+    Flag(String),
+    NotFlag(String),
+    Counter {
+        counter: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        at_least: Option<i32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        at_most: Option<i32>,
+    },
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Flag(flag) => write!(f, "{flag}"),
+            Condition::NotFlag(flag) => write!(f, "not {flag}"),
+            Condition::Counter { counter, at_least: Some(min), at_most: Some(max) } => write!(f, "{min} ≤ {counter} ≤ {max}"),
+            Condition::Counter { counter, at_least: Some(min), at_most: None } => write!(f, "{counter} ≥ {min}"),
+            Condition::Counter { counter, at_least: None, at_most: Some(max) } => write!(f, "{counter} ≤ {max}"),
+            Condition::Counter { counter, at_least: None, at_most: None } => write!(f, "any {counter}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "ChoicePromptData")]
+pub struct ChoicePrompt {
+    pub question: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_question: Option<String>,
+    pub options: Vec<String>,
+    pub pick: Pick,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Pick {
+    Any,
+    OneOf(Vec<String>),
+    AllOf { options: Vec<String>, ordered: bool },
+}
+
+// The YES/NO shape guides were written in before `ChoicePrompt` existed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PromptChoice {
     pub question: String,
     pub answer: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChoicePromptData {
+    Choice {
+        question: String,
+        #[serde(default)]
+        short_question: Option<String>,
+        options: Vec<String>,
+        pick: Pick,
+    },
+    YesNo(PromptChoice),
+}
+
+impl From<ChoicePromptData> for ChoicePrompt {
+    fn from(data: ChoicePromptData) -> Self {
+        match data {
+            ChoicePromptData::Choice { question, short_question, options, pick } => {
+                Self { question, short_question, options, pick }
+            }
+            ChoicePromptData::YesNo(prompt) => prompt.into(),
+        }
+    }
+}
+
+impl From<PromptChoice> for ChoicePrompt {
+    fn from(prompt: PromptChoice) -> Self {
+        Self::yes_no(&prompt.question, prompt.answer)
+    }
+}
+
+impl ChoicePrompt {
+    pub const YES: &'static str = "YES";
+    pub const NO: &'static str = "NO";
+
+    pub fn yes_no(question: &str, answer: bool) -> Self {
+        let answer = if answer { Self::YES } else { Self::NO };
+        Self {
+            question: question.to_string(),
+            short_question: None,
+            options: vec![Self::YES.to_string(), Self::NO.to_string()],
+            pick: Pick::OneOf(vec![answer.to_string()]),
+        }
+    }
+
+    pub fn with_short_question(mut self, short_question: &str) -> Self {
+        self.short_question = Some(short_question.to_string());
+        self
+    }
+
+    pub fn is_yes_no(&self) -> bool {
+        self.options == [Self::YES, Self::NO]
+    }
+
+    pub fn question_for(&self, spoilers: SpoilerLevel) -> &str {
+        match &self.short_question {
+            Some(short) if spoilers > SpoilerLevel::Off => short,
+            _ => &self.question,
+        }
+    }
+}
+
+impl Pick {
+    pub fn accepts(&self, picked: &[String]) -> bool {
+        match self {
+            Pick::Any => !picked.is_empty(),
+            Pick::OneOf(options) => picked.len() == 1 && options.contains(&picked[0]),
+            Pick::AllOf { options, ordered: true } => picked == options.as_slice(),
+            Pick::AllOf { options, ordered: false } => {
+                picked.len() == options.len() && options.iter().all(|option| picked.contains(option))
+            }
+        }
+    }
+
+    pub fn options(&self) -> &[String] {
+        match self {
+            Pick::Any => &[],
+            Pick::OneOf(options) | Pick::AllOf { options, .. } => options,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edition {
+    pub id: &'static str,
+    pub name: &'static str,
+}
+
+impl Route {
+    // Routes and steps without an edition list are in every edition.
+    pub fn for_edition(mut self, edition: &str) -> Option<Self> {
+        if !in_edition(&self.editions, edition) {
+            return None;
+        }
+        self.chapters.retain_mut(|chapter| {
+            let had_steps = !chapter.steps.is_empty();
+            chapter.steps.retain(|step| in_edition(&step.editions, edition));
+            !had_steps || !chapter.steps.is_empty()
+        });
+        Some(self)
+    }
+}
+
+fn in_edition(editions: &[String], edition: &str) -> bool {
+    editions.is_empty() || editions.iter().any(|e| e == edition)
+}
+
+// What has to be finished for an achievement to count as earned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Unlock {
+    Step(String),
+    Route(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlock: Option<Unlock>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub editions: Vec<String>,
+}
+
+// A TIPS entry, unlocked in `chapter` of any route or only of `routes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tip {
+    pub id: String,
+    pub term: String,
+    pub chapter: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub editions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollectionKind {
+    Gallery,
+    Movie,
+    Ending,
+}
+
+impl CollectionKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CollectionKind::Gallery => "CG",
+            CollectionKind::Movie => "Movie",
+            CollectionKind::Ending => "Ending",
+        }
+    }
+}
+
+// A gallery image, movie or ending list entry, collected once `unlock` is done.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionItem {
+    pub id: String,
+    pub name: String,
+    pub kind: CollectionKind,
+    pub unlock: Unlock,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub editions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SpoilerLevel {
+    /// Show every route, chapter and step
+    #[default]
+    Off,
+    /// Hide the names, descriptions and steps of locked routes
+    Routes,
+    /// Additionally hide chapters past the one currently being played
+    Chapters,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct GameData {
+    #[serde(skip)]
+    pub edition: Option<Edition>,
     pub routes: Vec<Route>,
     pub current_route: Option<usize>,
     pub progress: HashMap<String, bool>,
+    #[serde(default)]
+    pub notes: HashMap<String, String>,
+    #[serde(default)]
+    pub save_slots: HashMap<String, String>,
+    #[serde(default)]
+    pub choices: HashMap<String, Vec<Vec<String>>>,
+    #[serde(default)]
+    pub skipped: HashSet<String>,
+    #[serde(default)]
+    pub achievements: Vec<Achievement>,
+    #[serde(default)]
+    pub unlocked: HashSet<String>,
+    #[serde(default)]
+    pub tips: Vec<Tip>,
+    #[serde(default)]
+    pub tips_read: HashSet<String>,
+    #[serde(default)]
+    pub collection: Vec<CollectionItem>,
 }
 
 impl GameData {
     pub fn new() -> Self {
         Self {
+            edition: None,
             routes: Vec::new(),
             current_route: None,
             progress: HashMap::new(),
+            notes: HashMap::new(),
+            save_slots: HashMap::new(),
+            choices: HashMap::new(),
+            skipped: HashSet::new(),
+            achievements: Vec::new(),
+            unlocked: HashSet::new(),
+            tips: Vec::new(),
+            tips_read: HashSet::new(),
+            collection: Vec::new(),
         }
     }
 
-    pub fn mark_step_completed(&mut self, step_id: &str, completed: bool) {
+    pub fn with_routes(routes: Vec<Route>) -> Self {
+        Self {
+            routes,
+            ..Self::new()
+        }
+    }
+
+    // Keeps only the routes and steps of `edition`, dropping prerequisites on
+    // routes that edition doesn't have.
+    pub fn for_edition(routes: Vec<Route>, edition: Edition) -> Self {
+        let mut routes: Vec<Route> = routes.into_iter()
+            .filter_map(|route| route.for_edition(edition.id))
+            .collect();
+        let names: Vec<String> = routes.iter().map(|route| route.name.clone()).collect();
+        for route in &mut routes {
+            route.prerequisites.retain(|prereq| names.contains(prereq));
+        }
+
+        Self {
+            edition: Some(edition),
+            ..Self::with_routes(routes)
+        }
+    }
+
+    // Adds to the achievements already loaded, so bundled and player-supplied
+    // lists can be combined.
+    pub fn with_achievements(mut self, achievements: Vec<Achievement>) -> Self {
+        let edition = self.edition.map(|edition| edition.id);
+        self.achievements.extend(achievements.into_iter()
+            .filter(|achievement| edition.is_none_or(|edition| in_edition(&achievement.editions, edition))));
+        self
+    }
+
+    pub fn with_tips(mut self, tips: Vec<Tip>) -> Self {
+        let edition = self.edition.map(|edition| edition.id);
+        self.tips.extend(tips.into_iter()
+            .filter(|tip| edition.is_none_or(|edition| in_edition(&tip.editions, edition))));
+        self
+    }
+
+    pub fn with_collection(mut self, collection: Vec<CollectionItem>) -> Self {
+        let edition = self.edition.map(|edition| edition.id);
+        self.collection.extend(collection.into_iter()
+            .filter(|item| edition.is_none_or(|edition| in_edition(&item.editions, edition))));
+        self
+    }
+
+    pub fn guide_title(&self) -> String {
+        match self.edition {
+            Some(edition) => format!("{} - Route Guide", edition.name),
+            None => "Route Guide".to_string(),
+        }
+    }
+
+    pub fn route_index(&self, name: &str) -> Option<usize> {
+        self.routes.iter().position(|route| route.name == name)
+    }
+
+    pub fn steps(&self) -> impl Iterator<Item = &Step> {
+        self.routes.iter()
+            .flat_map(|route| &route.chapters)
+            .flat_map(|chapter| &chapter.steps)
+    }
+
+    pub fn step(&self, step_id: &str) -> Option<&Step> {
+        self.steps().find(|step| step.id == step_id)
+    }
+
+    pub fn clear_progress(&mut self) {
+        for step in self.routes.iter_mut()
+            .flat_map(|route| &mut route.chapters)
+            .flat_map(|chapter| &mut chapter.steps)
+        {
+            step.completed = false;
+        }
+        self.progress.clear();
+        self.notes.clear();
+        self.save_slots.clear();
+        self.choices.clear();
+        self.skipped.clear();
+        self.unlocked.clear();
+        self.tips_read.clear();
+    }
+
+    pub fn mark_step_completed(&mut self, step_id: &str, completed: bool) -> bool {
+        let Some(step) = self.routes.iter_mut()
+            .flat_map(|route| &mut route.chapters)
+            .flat_map(|chapter| &mut chapter.steps)
+            .find(|step| step.id == step_id)
+        else {
+            return false;
+        };
+
+        step.completed = completed;
         self.progress.insert(step_id.to_string(), completed);
-        
-        if let Some(route_idx) = self.current_route {
-            if let Some(route) = self.routes.get_mut(route_idx) {
-                for chapter in &mut route.chapters {
-                    for step in &mut chapter.steps {
-                        if step.id == step_id {
-                            step.completed = completed;
-                            break;
-                        }
-                    }
-                }
-            }
+        if completed {
+            self.skipped.remove(step_id);
+        }
+        true
+    }
+
+    pub fn is_skipped(&self, step_id: &str) -> bool {
+        self.skipped.contains(step_id)
+    }
+
+    // Skipping is a third state next to done and not done, so it clears
+    // completion rather than sitting alongside it.
+    pub fn set_skipped(&mut self, step_id: &str, skipped: bool) -> bool {
+        if self.step(step_id).is_none() {
+            return false;
+        }
+        if skipped {
+            self.mark_step_completed(step_id, false);
+            self.skipped.insert(step_id.to_string());
+        } else {
+            self.skipped.remove(step_id);
+        }
+        true
+    }
+
+    pub fn toggle_skipped(&mut self, step_id: &str) -> Option<bool> {
+        let skipped = !self.is_skipped(step_id);
+        self.set_skipped(step_id, skipped).then_some(skipped)
+    }
+
+    pub fn is_unlocked(&self, unlock: &Unlock) -> bool {
+        match unlock {
+            Unlock::Step(step_id) => self.step(step_id).is_some_and(|step| step.completed),
+            Unlock::Route(name) => self.route_index(name)
+                .is_some_and(|idx| self.get_completion_percentage(idx) >= 100.0),
         }
     }
 
+    // The route to play again for `unlock`, with the chapter when it's a step.
+    pub fn unlock_location(&self, unlock: &Unlock) -> Option<(usize, Option<usize>)> {
+        match unlock {
+            Unlock::Step(step_id) => self.routes.iter().enumerate().find_map(|(route_idx, route)| {
+                route.chapters.iter()
+                    .position(|chapter| chapter.steps.iter().any(|step| step.id == *step_id))
+                    .map(|chapter_idx| (route_idx, Some(chapter_idx)))
+            }),
+            Unlock::Route(name) => self.route_index(name).map(|route_idx| (route_idx, None)),
+        }
+    }
+
+    pub fn collection_progress(&self) -> (usize, usize) {
+        let collected = self.collection.iter().filter(|item| self.is_unlocked(&item.unlock)).count();
+        (collected, self.collection.len())
+    }
+
+    // Linked achievements follow their step or route; the rest, and any the
+    // player earned before tracking here, are marked by hand.
+    pub fn is_achievement_unlocked(&self, achievement: &Achievement) -> bool {
+        self.unlocked.contains(&achievement.id)
+            || achievement.unlock.as_ref().is_some_and(|unlock| self.is_unlocked(unlock))
+    }
+
+    pub fn toggle_achievement(&mut self, id: &str) -> Option<bool> {
+        self.achievements.iter().find(|achievement| achievement.id == id)?;
+        let unlocked = self.unlocked.insert(id.to_string());
+        if !unlocked {
+            self.unlocked.remove(id);
+        }
+        Some(unlocked)
+    }
+
+    pub fn achievement_progress(&self) -> (usize, usize) {
+        let unlocked = self.achievements.iter()
+            .filter(|achievement| self.is_achievement_unlocked(achievement))
+            .count();
+        (unlocked, self.achievements.len())
+    }
+
+    pub fn achievement_percentage(&self) -> f32 {
+        match self.achievement_progress() {
+            (_, 0) => 0.0,
+            (unlocked, total) => unlocked as f32 / total as f32 * 100.0,
+        }
+    }
+
+    pub fn is_tip_read(&self, id: &str) -> bool {
+        self.tips_read.contains(id)
+    }
+
+    pub fn toggle_tip(&mut self, id: &str) -> Option<bool> {
+        self.tips.iter().find(|tip| tip.id == id)?;
+        let read = self.tips_read.insert(id.to_string());
+        if !read {
+            self.tips_read.remove(id);
+        }
+        Some(read)
+    }
+
+    pub fn tip_progress(&self) -> (usize, usize) {
+        let read = self.tips.iter().filter(|tip| self.is_tip_read(&tip.id)).count();
+        (read, self.tips.len())
+    }
+
+    pub fn tip_chapters(&self) -> Vec<u32> {
+        let mut chapters: Vec<u32> = self.tips.iter().map(|tip| tip.chapter).collect();
+        chapters.sort_unstable();
+        chapters.dedup();
+        chapters
+    }
+
+    // Every required step, achievement and TIPS entry counts once towards the
+    // title as a whole.
+    pub fn title_progress(&self) -> (usize, usize) {
+        let (steps_done, steps) = self.required_progress(self.steps());
+        let (achievements_done, achievements) = self.achievement_progress();
+        let (tips_done, tips) = self.tip_progress();
+        (steps_done + achievements_done + tips_done, steps + achievements + tips)
+    }
+
+    pub fn title_completion(&self) -> f32 {
+        match self.title_progress() {
+            (_, 0) => 0.0,
+            (done, total) => done as f32 / total as f32 * 100.0,
+        }
+    }
+
+    pub fn toggle_step(&mut self, step_id: &str) -> Option<bool> {
+        let completed = !self.step(step_id)?.completed;
+        self.mark_step_completed(step_id, completed);
+        Some(completed)
+    }
+
+    pub fn note(&self, step_id: &str) -> Option<&str> {
+        self.notes.get(step_id).map(String::as_str)
+    }
+
+    pub fn set_note(&mut self, step_id: &str, note: &str) {
+        let note = note.trim();
+        if note.is_empty() {
+            self.notes.remove(step_id);
+        } else {
+            self.notes.insert(step_id.to_string(), note.to_string());
+        }
+    }
+
+    pub fn search_notes(&self, query: &str) -> Vec<(&str, &str)> {
+        let query = query.to_lowercase();
+        let mut matches: Vec<(&str, &str)> = self.notes.iter()
+            .filter(|(step_id, note)| {
+                note.to_lowercase().contains(&query)
+                    || step_id.to_lowercase().contains(&query)
+                    || self.step(step_id).is_some_and(|step| step.description.to_lowercase().contains(&query))
+            })
+            .map(|(step_id, note)| (step_id.as_str(), note.as_str()))
+            .collect();
+        matches.sort();
+        matches
+    }
+
+    pub fn save_slot(&self, step_id: &str) -> Option<&str> {
+        self.save_slots.get(step_id).map(String::as_str)
+    }
+
+    pub fn set_save_slot(&mut self, step_id: &str, slot: &str) {
+        let slot = slot.trim();
+        if slot.is_empty() {
+            self.save_slots.remove(step_id);
+        } else {
+            self.save_slots.insert(step_id.to_string(), slot.to_string());
+        }
+    }
+
+    pub fn reload_slot(&self, step_id: &str) -> Option<&str> {
+        let checkpoint = self.step(step_id)?.reload_from.as_deref()?;
+        self.save_slot(checkpoint)
+    }
+
+    pub fn choice(&self, step_id: &str) -> Option<&[Vec<String>]> {
+        self.choices.get(step_id).map(Vec::as_slice)
+    }
+
+    // Recording what was picked also marks the step as done; an empty choice
+    // just forgets it.
+    pub fn record_choice(&mut self, step_id: &str, choice: Vec<Vec<String>>) -> bool {
+        if self.step(step_id).is_none() {
+            return false;
+        }
+        if choice.iter().all(Vec::is_empty) {
+            self.choices.remove(step_id);
+        } else {
+            self.choices.insert(step_id.to_string(), choice);
+            self.mark_step_completed(step_id, true);
+        }
+        true
+    }
+
+    pub fn is_step_mismatched(&self, step: &Step) -> bool {
+        step.completed && self.choice(&step.id).is_some_and(|choice| !step.accepts(choice))
+    }
+
+    pub fn is_step_correct(&self, step: &Step) -> bool {
+        step.completed && !self.is_step_mismatched(step)
+    }
+
+    pub fn chapter_progress(&self, route_idx: usize, chapter_idx: usize) -> (usize, usize) {
+        let Some(chapter) = self.routes.get(route_idx).and_then(|route| route.chapters.get(chapter_idx)) else {
+            return (0, 0);
+        };
+        self.required_progress(&chapter.steps)
+    }
+
+    // Optional steps don't count towards completion either way.
+    fn required_progress<'a>(&self, steps: impl IntoIterator<Item = &'a Step>) -> (usize, usize) {
+        steps.into_iter()
+            .filter(|step| !step.optional)
+            .fold((0, 0), |(done, total), step| (done + usize::from(self.is_step_correct(step)), total + 1))
+    }
+
     pub fn get_completion_percentage(&self, route_idx: usize) -> f32 {
         if let Some(route) = self.routes.get(route_idx) {
-            let total_steps: usize = route.chapters.iter()
-                .map(|chapter| chapter.steps.len())
-                .sum();
-            
-            let completed_steps: usize = route.chapters.iter()
-                .flat_map(|chapter| &chapter.steps)
-                .filter(|step| step.completed)
-                .count();
-            
+            let (completed_steps, total_steps) = self.required_progress(route.chapters.iter().flat_map(|chapter| &chapter.steps));
+
             if total_steps == 0 {
                 0.0
             } else {
@@ -108,4 +718,51 @@ impl GameData {
             0.0
         }
     }
-}
\ No newline at end of file
+
+    pub fn is_route_available(&self, route_idx: usize) -> bool {
+        let Some(route) = self.routes.get(route_idx) else {
+            return true;
+        };
+
+        route.prerequisites.iter().all(|prereq| {
+            self.route_index(prereq)
+                .is_some_and(|idx| self.get_completion_percentage(idx) >= 100.0)
+        })
+    }
+
+    pub fn furthest_completed_chapter(&self, route_idx: usize) -> Option<usize> {
+        self.routes.get(route_idx)?
+            .chapters.iter()
+            .rposition(|chapter| !chapter.steps.is_empty() && chapter.steps.iter().all(|step| step.completed || step.optional))
+    }
+
+    pub fn is_route_hidden(&self, route_idx: usize, spoilers: SpoilerLevel) -> bool {
+        spoilers >= SpoilerLevel::Routes && !self.is_route_available(route_idx)
+    }
+
+    pub fn is_achievement_hidden(&self, achievement: &Achievement, spoilers: SpoilerLevel) -> bool {
+        spoilers > SpoilerLevel::Off && achievement.hidden && !self.is_achievement_unlocked(achievement)
+    }
+
+    // A TIPS entry limited to routes that are all still hidden would give
+    // those routes away. Routes this edition doesn't have can't be spoiled.
+    pub fn is_tip_hidden(&self, tip: &Tip, spoilers: SpoilerLevel) -> bool {
+        let mut routes = tip.routes.iter().filter_map(|name| self.route_index(name)).peekable();
+        routes.peek().is_some() && routes.all(|idx| self.is_route_hidden(idx, spoilers))
+    }
+
+    pub fn is_chapter_hidden(&self, route_idx: usize, chapter_idx: usize, spoilers: SpoilerLevel) -> bool {
+        if self.is_route_hidden(route_idx, spoilers) {
+            return true;
+        }
+        if spoilers < SpoilerLevel::Chapters {
+            return false;
+        }
+
+        let next_chapter = self.furthest_completed_chapter(route_idx).map_or(0, |idx| idx + 1);
+        chapter_idx > next_chapter
+    }
+}
+
+#[cfg(test)]
+mod tests;
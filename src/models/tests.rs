@@ -0,0 +1,375 @@
+use super::*;
+
+fn chaos_head() -> GameData {
+    crate::load_title("chaos_head").unwrap()
+}
+
+#[test]
+fn mark_step_completed_reaches_every_route() {
+    let mut game = chaos_head();
+    assert_eq!(game.current_route, None);
+
+    assert!(game.mark_step_completed("blue_sky_ending", true));
+    assert!(game.step("blue_sky_ending").unwrap().completed);
+    assert_eq!(game.progress.get("blue_sky_ending"), Some(&true));
+}
+
+#[test]
+fn unknown_steps_are_not_recorded() {
+    let mut game = chaos_head();
+
+    assert!(!game.mark_step_completed("no_such_step", true));
+    assert_eq!(game.toggle_step("no_such_step"), None);
+    assert!(game.progress.is_empty());
+}
+
+#[test]
+fn toggle_step_flips_completion() {
+    let mut game = chaos_head();
+
+    assert_eq!(game.toggle_step("silent_sky_ch6_save"), Some(true));
+    assert_eq!(game.toggle_step("silent_sky_ch6_save"), Some(false));
+    assert_eq!(game.progress.get("silent_sky_ch6_save"), Some(&false));
+}
+
+#[test]
+fn prerequisites_gate_route_availability() {
+    let mut game = chaos_head();
+    let crying_sky = game.route_index("Crying Sky").unwrap();
+    assert!(!game.is_route_available(crying_sky));
+
+    let silent_sky: Vec<String> = game.routes[0].chapters.iter()
+        .flat_map(|chapter| &chapter.steps)
+        .map(|step| step.id.clone())
+        .collect();
+    for id in &silent_sky {
+        game.mark_step_completed(id, true);
+    }
+
+    assert!(game.is_route_available(crying_sky));
+}
+
+#[test]
+fn blank_notes_are_removed() {
+    let mut game = chaos_head();
+
+    game.set_note("silent_sky_ch6_save", "  slot 3  ");
+    assert_eq!(game.note("silent_sky_ch6_save"), Some("slot 3"));
+
+    game.set_note("silent_sky_ch6_save", "   ");
+    assert_eq!(game.note("silent_sky_ch6_save"), None);
+}
+
+#[test]
+fn note_search_matches_text_ids_and_descriptions() {
+    let mut game = chaos_head();
+    game.set_note("silent_sky_ch6_save", "Saved in slot 3");
+    game.set_note("crying_sky_dt1", "Right after the rooftop scene");
+
+    assert_eq!(game.search_notes("ROOFTOP"), vec![("crying_sky_dt1", "Right after the rooftop scene")]);
+    assert_eq!(game.search_notes("o-front"), vec![("silent_sky_ch6_save", "Saved in slot 3")]);
+    assert_eq!(game.search_notes("").len(), 2);
+}
+
+#[test]
+fn reload_steps_find_the_checkpoint_slot() {
+    let mut game = chaos_head();
+    assert_eq!(game.reload_slot("silent_sky_ch6_bad_ending"), None);
+
+    game.set_save_slot("silent_sky_ch6_save", "Slot 12");
+    assert_eq!(game.reload_slot("silent_sky_ch6_bad_ending"), Some("Slot 12"));
+    assert_eq!(game.reload_slot("silent_sky_ch6_proceed"), Some("Slot 12"));
+    assert_eq!(game.reload_slot("silent_sky_ch6_save"), None);
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn picks_accept_the_required_answers() {
+    assert!(Pick::Any.accepts(&strings(&["anything"])));
+    assert!(!Pick::Any.accepts(&[]));
+
+    let one_of = Pick::OneOf(strings(&["A", "B"]));
+    assert!(one_of.accepts(&strings(&["B"])));
+    assert!(!one_of.accepts(&strings(&["C"])));
+    assert!(!one_of.accepts(&strings(&["A", "B"])));
+
+    let ordered = Pick::AllOf { options: strings(&["A", "B"]), ordered: true };
+    assert!(ordered.accepts(&strings(&["A", "B"])));
+    assert!(!ordered.accepts(&strings(&["B", "A"])));
+
+    let unordered = Pick::AllOf { options: strings(&["A", "B"]), ordered: false };
+    assert!(unordered.accepts(&strings(&["B", "A"])));
+    assert!(!unordered.accepts(&strings(&["A"])));
+}
+
+#[test]
+fn legacy_yes_no_prompts_are_migrated() {
+    let json = r#"{ "YesNoPrompts": { "prompts": [{ "question": "Q", "answer": false }] } }"#;
+    let step_type: StepType = serde_json::from_str(json).unwrap();
+
+    let StepType::Choices { prompts } = step_type else {
+        panic!("expected choices, got {step_type:?}");
+    };
+    assert_eq!(prompts, vec![ChoicePrompt::yes_no("Q", false)]);
+    assert!(prompts[0].is_yes_no());
+    assert_eq!(prompts[0].pick, Pick::OneOf(strings(&["NO"])));
+}
+
+#[test]
+fn choices_round_trip_in_the_new_shape() {
+    let step_type = StepType::Choices {
+        prompts: vec![ChoicePrompt {
+            question: "Reply to Maho".to_string(),
+            short_question: None,
+            options: strings(&["Sticker", "Text", "Ignore"]),
+            pick: Pick::Any,
+        }],
+    };
+    let json = serde_json::to_string(&step_type).unwrap();
+    assert!(json.starts_with(r#"{"Choices":"#));
+
+    let StepType::Choices { prompts } = serde_json::from_str(&json).unwrap() else {
+        panic!("expected choices");
+    };
+    assert_eq!(prompts[0].pick, Pick::Any);
+}
+
+#[test]
+fn short_questions_are_used_when_hiding_spoilers() {
+    let prompt = ChoicePrompt::yes_no("Do you accept what really happened on the rooftop?", true)
+        .with_short_question("Do you accept it?");
+
+    assert_eq!(prompt.question_for(SpoilerLevel::Off), "Do you accept what really happened on the rooftop?");
+    assert_eq!(prompt.question_for(SpoilerLevel::Routes), "Do you accept it?");
+    assert_eq!(ChoicePrompt::yes_no("Q", true).question_for(SpoilerLevel::Chapters), "Q");
+
+    let json = serde_json::to_string(&prompt).unwrap();
+    assert!(json.contains(r#""short_question":"Do you accept it?""#));
+    assert_eq!(serde_json::from_str::<ChoicePrompt>(&json).unwrap(), prompt);
+}
+
+#[test]
+fn steps_accept_only_the_guide_answers() {
+    let game = chaos_head();
+    let trigger = game.step("daydream_dt1").unwrap();
+    assert!(trigger.accepts(&[strings(&["Negative"])]));
+    assert!(!trigger.accepts(&[strings(&["Positive"])]));
+    assert_eq!(trigger.choice_options(), [strings(&["Positive", "Negative", "Neutral"])]);
+
+    let instruction = game.step("silent_sky_ch2_hospital").unwrap();
+    assert!(instruction.choice_options().is_empty());
+    assert!(instruction.accepts(&[]));
+}
+
+#[test]
+fn recording_a_choice_completes_the_step() {
+    let mut game = chaos_head();
+    assert!(!game.record_choice("no_such_step", vec![strings(&["Positive"])]));
+
+    assert!(game.record_choice("daydream_dt1", vec![strings(&["Negative"])]));
+    assert!(game.step("daydream_dt1").unwrap().completed);
+    assert_eq!(game.choice("daydream_dt1"), Some([strings(&["Negative"])].as_slice()));
+
+    game.record_choice("daydream_dt1", vec![]);
+    assert_eq!(game.choice("daydream_dt1"), None);
+    assert!(game.step("daydream_dt1").unwrap().completed);
+}
+
+#[test]
+fn wrong_choices_do_not_count_towards_completion() {
+    let mut game = chaos_head();
+    let daydream = game.route_index("Daydream").unwrap();
+
+    game.record_choice("daydream_dt1", vec![strings(&["Negative"])]);
+    let correct = game.get_completion_percentage(daydream);
+    assert!(correct > 0.0);
+
+    game.record_choice("daydream_dt1", vec![strings(&["Positive"])]);
+    let step = game.step("daydream_dt1").unwrap();
+    assert!(game.is_step_mismatched(step));
+    assert!(!game.is_step_correct(step));
+    assert_eq!(game.get_completion_percentage(daydream), 0.0);
+}
+
+#[test]
+fn optional_and_skipped_steps_do_not_hold_back_completion() {
+    let mut game = chaos_head();
+    let silent_sky = game.route_index("Silent Sky").unwrap();
+    for id in ["silent_sky_ch1_swimsuit", "silent_sky_ch2_hospital", "silent_sky_ch6_save", "silent_sky_ch6_proceed"] {
+        game.mark_step_completed(id, true);
+    }
+    assert_eq!(game.chapter_progress(silent_sky, 2), (2, 2));
+    assert!(game.get_completion_percentage(silent_sky) < 100.0);
+
+    game.set_skipped("silent_sky_ch10_ending", true);
+    assert!(game.is_skipped("silent_sky_ch10_ending"));
+    assert!(game.get_completion_percentage(silent_sky) < 100.0);
+
+    game.mark_step_completed("silent_sky_ch10_ending", true);
+    assert!(!game.is_skipped("silent_sky_ch10_ending"));
+    assert_eq!(game.get_completion_percentage(silent_sky), 100.0);
+}
+
+#[test]
+fn skipping_a_step_clears_its_completion() {
+    let mut game = chaos_head();
+    game.mark_step_completed("silent_sky_ch6_bad_ending", true);
+
+    assert_eq!(game.toggle_skipped("silent_sky_ch6_bad_ending"), Some(true));
+    assert!(!game.step("silent_sky_ch6_bad_ending").unwrap().completed);
+    assert_eq!(game.toggle_skipped("silent_sky_ch6_bad_ending"), Some(false));
+    assert_eq!(game.toggle_skipped("no_such_step"), None);
+}
+
+#[test]
+fn editions_filter_routes_steps_and_prerequisites() {
+    let noah = chaos_head();
+    assert_eq!(noah.edition.map(|edition| edition.name), Some("Chaos;Head NoAH"));
+    assert_eq!(noah.routes.len(), 9);
+    assert!(noah.step("blue_sky_ending").is_some());
+    assert!(noah.step("blue_sky_ending_original").is_none());
+
+    let original = crate::load_edition("chaos_head", Some("original")).unwrap();
+    assert_eq!(original.guide_title(), "Chaos;Head - Route Guide");
+    let names: Vec<&str> = original.routes.iter().map(|route| route.name.as_str()).collect();
+    assert_eq!(names, ["Silent Sky", "Crying Sky", "Blue Sky"]);
+    let blue_sky = original.route_index("Blue Sky").unwrap();
+    assert_eq!(original.routes[blue_sky].prerequisites, strings(&["Silent Sky", "Crying Sky"]));
+    assert!(original.step("blue_sky_ending").is_none());
+    assert!(original.step("blue_sky_ending_original").is_some());
+
+    assert!(crate::load_edition("chaos_head", Some("remastered")).is_none());
+}
+
+fn achievement(id: &str, unlock: Option<Unlock>, editions: &[&str]) -> Achievement {
+    Achievement {
+        id: id.to_string(),
+        name: id.to_string(),
+        description: String::new(),
+        hidden: false,
+        unlock,
+        editions: strings(editions),
+    }
+}
+
+#[test]
+fn achievements_unlock_with_their_step_or_route() {
+    let mut game = chaos_head().with_achievements(vec![
+        achievement("swimsuit", Some(Unlock::Step("silent_sky_ch1_swimsuit".to_string())), &[]),
+        achievement("silent_sky", Some(Unlock::Route("Silent Sky".to_string())), &[]),
+        achievement("manual", None, &[]),
+    ]);
+    assert_eq!(game.achievement_progress(), (0, 3));
+
+    game.mark_step_completed("silent_sky_ch1_swimsuit", true);
+    assert!(game.is_achievement_unlocked(&game.achievements[0]));
+    assert!(!game.is_achievement_unlocked(&game.achievements[1]));
+
+    let silent_sky: Vec<String> = game.routes[0].chapters.iter()
+        .flat_map(|chapter| &chapter.steps)
+        .map(|step| step.id.clone())
+        .collect();
+    for id in &silent_sky {
+        game.mark_step_completed(id, true);
+    }
+    assert!(game.is_achievement_unlocked(&game.achievements[1]));
+
+    assert_eq!(game.toggle_achievement("manual"), Some(true));
+    assert_eq!(game.toggle_achievement("missing"), None);
+    assert_eq!(game.achievement_progress(), (3, 3));
+    assert_eq!(game.achievement_percentage(), 100.0);
+}
+
+#[test]
+fn achievements_are_filtered_by_edition() {
+    let game = crate::load_edition("chaos_head", Some("original")).unwrap().with_achievements(vec![
+        achievement("both", None, &[]),
+        achievement("noah_only", None, &["noah"]),
+    ]);
+
+    let ids: Vec<&str> = game.achievements.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["both"]);
+}
+
+fn tip(id: &str, chapter: u32, routes: &[&str]) -> Tip {
+    Tip { id: id.to_string(), term: id.to_string(), chapter, routes: strings(routes), editions: vec![] }
+}
+
+#[test]
+fn tips_count_towards_title_completion() {
+    let mut game = chaos_head().with_tips(vec![tip("gigalomaniac", 1, &[]), tip("di_sword", 2, &[]), tip("norn", 2, &[])]);
+    let (_, steps) = chaos_head().title_progress();
+    assert_eq!(game.title_progress(), (0, steps + 3));
+    assert_eq!(game.tip_chapters(), [1, 2]);
+
+    assert_eq!(game.toggle_tip("norn"), Some(true));
+    assert_eq!(game.toggle_tip("missing"), None);
+    game.mark_step_completed("silent_sky_ch1_swimsuit", true);
+    assert_eq!(game.tip_progress(), (1, 3));
+    assert_eq!(game.title_progress(), (2, steps + 3));
+
+    assert_eq!(game.toggle_tip("norn"), Some(false));
+    assert!(!game.is_tip_read("norn"));
+}
+
+#[test]
+fn tips_on_hidden_routes_are_hidden() {
+    let game = chaos_head();
+    let anywhere = tip("anywhere", 1, &[]);
+    let crying_sky = tip("crying_sky", 1, &["Crying Sky"]);
+    let either = tip("either", 1, &["Silent Sky", "Crying Sky"]);
+
+    assert!(!game.is_tip_hidden(&anywhere, SpoilerLevel::Routes));
+    assert!(game.is_tip_hidden(&crying_sky, SpoilerLevel::Routes));
+    assert!(!game.is_tip_hidden(&crying_sky, SpoilerLevel::Off));
+    assert!(!game.is_tip_hidden(&either, SpoilerLevel::Routes));
+}
+
+#[test]
+fn tips_for_routes_outside_the_edition_follow_the_spoiler_level() {
+    let original = crate::load_edition("chaos_head", Some("original")).unwrap();
+    assert!(original.route_index("Daydream").is_none());
+    let daydream = tip("daydream", 1, &["Daydream"]);
+    let with_crying_sky = tip("with_crying_sky", 1, &["Daydream", "Crying Sky"]);
+
+    assert!(!original.is_tip_hidden(&daydream, SpoilerLevel::Off));
+    assert!(!original.is_tip_hidden(&daydream, SpoilerLevel::Routes));
+    assert!(!original.is_tip_hidden(&with_crying_sky, SpoilerLevel::Off));
+    assert!(original.is_tip_hidden(&with_crying_sky, SpoilerLevel::Routes));
+}
+
+#[test]
+fn bundled_endings_follow_the_edition_routes() {
+    let noah = chaos_head();
+    let original = crate::load_edition("chaos_head", Some("original")).unwrap();
+
+    assert_eq!(noah.collection.len(), noah.routes.len());
+    assert_eq!(original.collection.len(), original.routes.len());
+    assert!(noah.collection.iter().all(|item| item.kind == CollectionKind::Ending));
+    assert_eq!(noah.collection[0].id, "ending_silent_sky");
+}
+
+#[test]
+fn collection_items_point_at_the_route_to_replay() {
+    let mut game = chaos_head().with_collection(vec![CollectionItem {
+        id: "swimsuit_cg".to_string(),
+        name: "Swimsuit".to_string(),
+        kind: CollectionKind::Gallery,
+        unlock: Unlock::Step("silent_sky_ch2_hospital".to_string()),
+        editions: vec![],
+    }]);
+
+    let endings = game.routes.len();
+    assert_eq!(game.collection.len(), endings + 1);
+    let unlock = game.collection[endings].unlock.clone();
+    assert_eq!(game.unlock_location(&unlock), Some((0, Some(1))));
+    assert_eq!(game.unlock_location(&Unlock::Route("Daydream".to_string())), Some((2, None)));
+    assert_eq!(game.unlock_location(&Unlock::Step("missing".to_string())), None);
+
+    assert_eq!(game.collection_progress(), (0, endings + 1));
+    game.mark_step_completed("silent_sky_ch2_hospital", true);
+    assert_eq!(game.collection_progress(), (1, endings + 1));
+}
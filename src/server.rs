@@ -0,0 +1,214 @@
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use anyhow::{anyhow, Result};
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+use crate::models::*;
+use crate::storage::ProgressStore;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+    pub location: Option<String>,
+}
+
+impl Reply {
+    fn html(body: String) -> Self {
+        Self { status: 200, content_type: "text/html; charset=utf-8", body, location: None }
+    }
+
+    fn json(value: serde_json::Value) -> Self {
+        Self { status: 200, content_type: "application/json", body: value.to_string(), location: None }
+    }
+
+    fn redirect(location: &str) -> Self {
+        Self { status: 303, content_type: "text/plain; charset=utf-8", body: String::new(), location: Some(location.to_string()) }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self { status, content_type: "application/json", body: json!({ "error": message }).to_string(), location: None }
+    }
+}
+
+pub struct GuideServer {
+    title: String,
+    edition: String,
+    store: ProgressStore,
+}
+
+impl GuideServer {
+    pub fn new(title: &str, edition: &str, store: ProgressStore) -> Self {
+        Self { title: title.to_string(), edition: edition.to_string(), store }
+    }
+
+    pub fn run(&self, addr: SocketAddr) -> Result<()> {
+        let server = Server::http(addr).map_err(|e| anyhow!("failed to bind {addr}: {e}"))?;
+        println!("Serving {} on http://{addr}", self.title);
+
+        for request in server.incoming_requests() {
+            let reply = self.handle(request.method(), request.url())
+                .unwrap_or_else(|e| Reply::error(500, &format!("{e:#}")));
+            if let Err(e) = respond(request, reply) {
+                eprintln!("failed to respond: {e:#}");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn handle(&self, method: &Method, url: &str) -> Result<Reply> {
+        let path = url.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let reply = match (method, segments.as_slice()) {
+            (Method::Get, []) => Reply::html(render_index(&self.load()?)),
+            (Method::Get, ["api", "routes"]) => Reply::json(routes_json(&self.load()?)),
+            (Method::Get, ["api", "progress"]) => Reply::json(json!(self.load()?.progress)),
+            (Method::Post, ["api", "steps", step_id, "toggle"]) => match self.toggle(step_id)? {
+                Some(completed) => Reply::json(json!({ "id": step_id, "completed": completed })),
+                None => Reply::error(404, &format!("unknown step \"{step_id}\"")),
+            },
+            (Method::Post, ["steps", step_id, "toggle"]) => match self.toggle(step_id)? {
+                Some(_) => Reply::redirect(&format!("/#{step_id}")),
+                None => Reply::error(404, &format!("unknown step \"{step_id}\"")),
+            },
+            (_, [] | ["api", "routes" | "progress"] | ["api", "steps", _, "toggle"] | ["steps", _, "toggle"]) => {
+                Reply::error(405, "method not allowed")
+            }
+            _ => Reply::error(404, "not found"),
+        };
+        Ok(reply)
+    }
+
+    fn load(&self) -> Result<GameData> {
+        let mut game = crate::open_edition(&self.title, Some(&self.edition))?
+            .ok_or_else(|| anyhow!("\"{}\" has no route data for edition \"{}\"", self.title, self.edition))?;
+        self.store.load(&mut game)?;
+        Ok(game)
+    }
+
+    fn toggle(&self, step_id: &str) -> Result<Option<bool>> {
+        let mut game = self.load()?;
+        let completed = game.toggle_step(step_id);
+        if completed.is_some() {
+            self.store.save(&game)?;
+        }
+        Ok(completed)
+    }
+}
+
+fn respond(request: Request, reply: Reply) -> Result<()> {
+    let mut response = Response::from_string(reply.body)
+        .with_status_code(reply.status)
+        .with_header(Header::from_bytes("Content-Type", reply.content_type).expect("static header is valid"));
+    if let Some(location) = reply.location {
+        response = response.with_header(Header::from_bytes("Location", location).expect("step ids are valid header values"));
+    }
+    request.respond(response)?;
+    Ok(())
+}
+
+fn routes_json(game: &GameData) -> serde_json::Value {
+    let routes: Vec<_> = game.routes.iter().enumerate().map(|(i, route)| {
+        json!({
+            "name": route.name,
+            "description": route.description,
+            "prerequisites": route.prerequisites,
+            "available": game.is_route_available(i),
+            "completion": game.get_completion_percentage(i),
+            "chapters": route.chapters.iter().map(|chapter| json!({
+                "number": chapter.number,
+                "name": chapter.name,
+                "steps": chapter.steps.iter().map(|step| json!({
+                    "id": step.id,
+                    "description": step.description,
+                    "completed": step.completed,
+                    "optional": step.optional,
+                    "skipped": game.is_skipped(&step.id),
+                    "note": game.note(&step.id),
+                    "save_slot": game.save_slot(&step.id),
+                    "reload_from": step.reload_from,
+                    "reload_slot": game.reload_slot(&step.id),
+                    "choice": game.choice(&step.id),
+                    "mismatched": game.is_step_mismatched(step),
+                })).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
+        })
+    }).collect();
+
+    json!(routes)
+}
+
+fn render_index(game: &GameData) -> String {
+    let heading = escape(&game.guide_title());
+    let mut html = String::new();
+
+    let _ = write!(html, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{heading}</title>\n");
+    html.push_str(STYLE);
+    let _ = write!(html, "</head>\n<body>\n<h1>{heading}</h1>\n");
+
+    for (i, route) in game.routes.iter().enumerate() {
+        let class = if game.is_route_available(i) { "route" } else { "route locked" };
+        let _ = write!(
+            html,
+            "<section class=\"{class}\">\n<h2>{} <small>{:.0}%</small></h2>\n<p>{}</p>\n",
+            escape(&route.name),
+            game.get_completion_percentage(i),
+            escape(&route.description),
+        );
+
+        for chapter in &route.chapters {
+            let _ = write!(html, "<h3>{}</h3>\n<ul>\n", escape(&chapter.name));
+            for step in &chapter.steps {
+                let (class, symbol) = if game.is_step_mismatched(step) {
+                    ("wrong", "✗")
+                } else if step.completed {
+                    ("done", "✓")
+                } else if game.is_skipped(&step.id) {
+                    ("skipped", "↷")
+                } else {
+                    ("pending", "○")
+                };
+                let _ = writeln!(
+                    html,
+                    "<li id=\"{id}\" class=\"{class}\"><form method=\"post\" action=\"/steps/{id}/toggle\"><button>{symbol}</button> {}</form></li>",
+                    escape(&step.description),
+                    id = escape(&step.id),
+                );
+                if let Some(note) = game.note(&step.id) {
+                    let _ = writeln!(html, "<li class=\"note\">✎ {}</li>", escape(note));
+                }
+            }
+            html.push_str("</ul>\n");
+        }
+        html.push_str("</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+const STYLE: &str = "<style>
+body { font-family: sans-serif; max-width: 60em; margin: 2em auto; }
+.locked { opacity: 0.5; }
+.done { color: green; }
+.wrong { color: red; }
+.skipped { color: gray; }
+.note { margin-left: 2.5em; font-style: italic; }
+li { list-style: none; }
+form { margin: 0; }
+button { width: 2em; }
+</style>
+";
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,71 @@
+use super::*;
+use crate::testing::TempDir;
+
+fn server(name: &str) -> (TempDir, GuideServer) {
+    let dir = TempDir::new(name);
+    let server = GuideServer::new("chaos_head", "noah", ProgressStore::at(dir.join("chaos_head.json")));
+    (dir, server)
+}
+
+fn get(server: &GuideServer, url: &str) -> Reply {
+    server.handle(&Method::Get, url).unwrap()
+}
+
+fn post(server: &GuideServer, url: &str) -> Reply {
+    server.handle(&Method::Post, url).unwrap()
+}
+
+#[test]
+fn index_lists_routes_and_toggle_forms() {
+    let (_dir, server) = server("server-index");
+    let reply = get(&server, "/");
+
+    assert_eq!(reply.status, 200);
+    assert!(reply.content_type.starts_with("text/html"));
+    assert!(reply.body.contains("<h1>Chaos;Head NoAH - Route Guide</h1>"));
+    assert!(reply.body.contains("A Bloody Contract for Your Sins"));
+    assert!(reply.body.contains("action=\"/steps/silent_sky_ch6_save/toggle\""));
+    assert!(reply.body.contains("you&#39;d like"));
+}
+
+#[test]
+fn api_toggle_is_persisted() {
+    let (_dir, server) = server("server-api-toggle");
+
+    let reply = post(&server, "/api/steps/silent_sky_ch6_save/toggle");
+    assert_eq!(reply.status, 200);
+    assert_eq!(reply.body, r#"{"completed":true,"id":"silent_sky_ch6_save"}"#);
+
+    let progress = get(&server, "/api/progress");
+    assert_eq!(progress.body, r#"{"silent_sky_ch6_save":true}"#);
+
+    let routes: serde_json::Value = serde_json::from_str(&get(&server, "/api/routes").body).unwrap();
+    assert_eq!(routes[0]["name"], "Silent Sky");
+    assert_eq!(routes[0]["chapters"][2]["steps"][0]["completed"], true);
+    assert_eq!(routes[0]["chapters"][2]["steps"][0]["note"], serde_json::Value::Null);
+    assert_eq!(routes[0]["chapters"][2]["steps"][0]["choice"], serde_json::Value::Null);
+    assert_eq!(routes[0]["chapters"][2]["steps"][0]["mismatched"], false);
+    assert_eq!(routes[0]["chapters"][2]["steps"][1]["optional"], true);
+    assert_eq!(routes[0]["chapters"][2]["steps"][1]["skipped"], false);
+    assert_eq!(routes[1]["available"], false);
+}
+
+#[test]
+fn form_toggle_redirects_back_to_the_step() {
+    let (_dir, server) = server("server-form-toggle");
+
+    let reply = post(&server, "/steps/silent_sky_ch1_swimsuit/toggle");
+    assert_eq!(reply.status, 303);
+    assert_eq!(reply.location.as_deref(), Some("/#silent_sky_ch1_swimsuit"));
+    assert!(get(&server, "/").body.contains("<li id=\"silent_sky_ch1_swimsuit\" class=\"done\">"));
+}
+
+#[test]
+fn unknown_steps_and_paths_are_not_found() {
+    let (_dir, server) = server("server-not-found");
+
+    assert_eq!(post(&server, "/api/steps/nope/toggle").status, 404);
+    assert_eq!(get(&server, "/nope").status, 404);
+    assert_eq!(get(&server, "/api/steps/silent_sky_ch6_save/toggle").status, 405);
+    assert_eq!(post(&server, "/api/routes").status, 405);
+}
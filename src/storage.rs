@@ -0,0 +1,149 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use crate::models::GameData;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedProgress {
+    pub progress: HashMap<String, bool>,
+    pub notes: HashMap<String, String>,
+    pub save_slots: HashMap<String, String>,
+    pub choices: HashMap<String, Vec<Vec<String>>>,
+    pub skipped: HashSet<String>,
+    pub achievements: HashSet<String>,
+    pub tips: HashSet<String>,
+}
+
+impl SavedProgress {
+    pub fn from_game(game: &GameData) -> Self {
+        Self {
+            progress: game.progress.clone(),
+            notes: game.notes.clone(),
+            save_slots: game.save_slots.clone(),
+            choices: game.choices.clone(),
+            skipped: game.skipped.clone(),
+            achievements: game.unlocked.clone(),
+            tips: game.tips_read.clone(),
+        }
+    }
+
+    pub fn apply(self, game: &mut GameData) {
+        for (step_id, completed) in self.progress {
+            if !game.mark_step_completed(&step_id, completed) {
+                game.progress.insert(step_id, completed);
+            }
+        }
+        game.notes.extend(self.notes);
+        game.save_slots.extend(self.save_slots);
+        game.choices.extend(self.choices);
+        game.skipped.extend(self.skipped);
+        game.unlocked.extend(self.achievements);
+        game.tips_read.extend(self.tips);
+    }
+
+    // Applies whatever `mine` changed since `base` on top of `self`, keeping
+    // everything else `self` has. Used when another process saved first.
+    pub fn rebase(mut self, base: &SavedProgress, mine: SavedProgress) -> SavedProgress {
+        rebase_map(&mut self.progress, &base.progress, mine.progress);
+        rebase_map(&mut self.notes, &base.notes, mine.notes);
+        rebase_map(&mut self.save_slots, &base.save_slots, mine.save_slots);
+        rebase_map(&mut self.choices, &base.choices, mine.choices);
+        rebase_set(&mut self.skipped, &base.skipped, mine.skipped);
+        rebase_set(&mut self.achievements, &base.achievements, mine.achievements);
+        rebase_set(&mut self.tips, &base.tips, mine.tips);
+        self
+    }
+}
+
+fn rebase_map<V: PartialEq>(theirs: &mut HashMap<String, V>, base: &HashMap<String, V>, mine: HashMap<String, V>) {
+    theirs.retain(|key, _| !base.contains_key(key) || mine.contains_key(key));
+    for (key, value) in mine {
+        if base.get(&key) != Some(&value) {
+            theirs.insert(key, value);
+        }
+    }
+}
+
+fn rebase_set(theirs: &mut HashSet<String>, base: &HashSet<String>, mine: HashSet<String>) {
+    theirs.retain(|key| !base.contains(key) || mine.contains(key));
+    theirs.extend(mine.into_iter().filter(|key| !base.contains(key)));
+}
+
+#[derive(Debug, Clone)]
+pub struct ProgressStore {
+    path: PathBuf,
+}
+
+impl ProgressStore {
+    pub fn for_title(title: &str, edition: &str) -> Result<Self> {
+        let data_dir = dirs::data_dir().context("could not determine the user data directory")?;
+        Ok(Self::at(data_dir.join("sciadv").join(Self::file_name(title, edition))))
+    }
+
+    // Progress saved before editions existed belongs to the default edition,
+    // so that one keeps the plain `<title>.json` name.
+    pub fn file_name(title: &str, edition: &str) -> String {
+        if crate::data::editions(title).first().is_some_and(|default| default.id == edition) {
+            format!("{title}.json")
+        } else {
+            format!("{title}.{edition}.json")
+        }
+    }
+
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self, game: &mut GameData) -> Result<()> {
+        if let Some(contents) = self.read()? {
+            self.parse(&contents)?.apply(game);
+        }
+        Ok(())
+    }
+
+    // The file exactly as it is on disk, so callers can tell whether anyone
+    // else has written it since they last looked.
+    pub fn read(&self) -> Result<Option<String>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        fs::read_to_string(&self.path)
+            .map(Some)
+            .with_context(|| format!("failed to read progress file {}", self.path.display()))
+    }
+
+    pub fn parse(&self, contents: &str) -> Result<SavedProgress> {
+        serde_json::from_str(contents)
+            .with_context(|| format!("failed to parse progress file {}", self.path.display()))
+    }
+
+    // Both return what was written, for comparing with a later `read`.
+    pub fn save(&self, game: &GameData) -> Result<String> {
+        self.save_progress(&SavedProgress::from_game(game))
+    }
+
+    pub fn save_progress(&self, saved: &SavedProgress) -> Result<String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+
+        let contents = serde_json::to_string_pretty(saved)?;
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, &contents)
+            .with_context(|| format!("failed to write progress file {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("failed to write progress file {}", self.path.display()))?;
+        Ok(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests;
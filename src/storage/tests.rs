@@ -0,0 +1,93 @@
+use super::*;
+use crate::testing::TempDir;
+
+fn temp_store(name: &str) -> (TempDir, ProgressStore) {
+    let dir = TempDir::new(name);
+    let store = ProgressStore::at(dir.join("progress.json"));
+    (dir, store)
+}
+
+#[test]
+fn progress_files_are_kept_per_edition() {
+    assert_eq!(ProgressStore::file_name("chaos_head", "noah"), "chaos_head.json");
+    assert_eq!(ProgressStore::file_name("chaos_head", "original"), "chaos_head.original.json");
+}
+
+#[test]
+fn missing_file_loads_as_empty_progress() {
+    let (_dir, store) = temp_store("missing");
+    let mut game = crate::load_title("chaos_head").unwrap();
+
+    store.load(&mut game).unwrap();
+    assert!(game.progress.is_empty());
+}
+
+#[test]
+fn progress_round_trips_through_disk() {
+    let (_dir, store) = temp_store("round-trip");
+    let mut game = crate::load_title("chaos_head").unwrap();
+    game.mark_step_completed("silent_sky_ch6_save", true);
+    game.progress.insert("from_another_edition".to_string(), true);
+    game.set_note("silent_sky_ch6_save", "Slot 3");
+    game.record_choice("daydream_dt1", vec![vec!["Positive".to_string()]]);
+    game.set_skipped("silent_sky_ch6_bad_ending", true);
+    game.unlocked.insert("first_trophy".to_string());
+    game.tips_read.insert("gigalomaniac".to_string());
+    store.save(&game).unwrap();
+
+    let mut reloaded = crate::load_title("chaos_head").unwrap();
+    store.load(&mut reloaded).unwrap();
+
+    assert!(reloaded.step("silent_sky_ch6_save").unwrap().completed);
+    assert_eq!(reloaded.progress, game.progress);
+    assert_eq!(reloaded.note("silent_sky_ch6_save"), Some("Slot 3"));
+    assert_eq!(reloaded.choice("daydream_dt1"), game.choice("daydream_dt1"));
+    assert!(reloaded.step("daydream_dt1").unwrap().completed);
+    assert!(reloaded.is_skipped("silent_sky_ch6_bad_ending"));
+    assert_eq!(reloaded.unlocked, game.unlocked);
+    assert_eq!(reloaded.tips_read, game.tips_read);
+}
+
+#[test]
+fn files_without_notes_still_load() {
+    let (_dir, store) = temp_store("no-notes");
+    fs::create_dir_all(store.path().parent().unwrap()).unwrap();
+    fs::write(store.path(), r#"{ "progress": { "silent_sky_ch6_save": true } }"#).unwrap();
+
+    let mut game = crate::load_title("chaos_head").unwrap();
+    store.load(&mut game).unwrap();
+    assert!(game.step("silent_sky_ch6_save").unwrap().completed);
+    assert!(game.notes.is_empty());
+}
+
+#[test]
+fn corrupt_files_report_their_path() {
+    let (_dir, store) = temp_store("corrupt");
+    fs::create_dir_all(store.path().parent().unwrap()).unwrap();
+    fs::write(store.path(), "not json").unwrap();
+
+    let err = store.load(&mut GameData::new()).unwrap_err();
+    assert!(err.to_string().contains("progress.json"));
+}
+
+#[test]
+fn rebasing_keeps_both_sides_edits() {
+    let strings = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<HashSet<_>>();
+    let base = SavedProgress {
+        progress: HashMap::from([("a".to_string(), true), ("b".to_string(), true)]),
+        skipped: strings(&["x"]),
+        ..SavedProgress::default()
+    };
+    let mut mine = base.clone();
+    mine.progress.remove("a");
+    mine.progress.insert("c".to_string(), true);
+    mine.notes.insert("c".to_string(), "mine".to_string());
+    let mut theirs = base.clone();
+    theirs.progress.insert("d".to_string(), true);
+    theirs.skipped.insert("y".to_string());
+
+    let merged = theirs.rebase(&base, mine);
+    assert_eq!(merged.progress, HashMap::from([("b".to_string(), true), ("c".to_string(), true), ("d".to_string(), true)]));
+    assert_eq!(merged.notes, HashMap::from([("c".to_string(), "mine".to_string())]));
+    assert_eq!(merged.skipped, strings(&["x", "y"]));
+}
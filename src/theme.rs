@@ -0,0 +1,160 @@
+use clap::ValueEnum;
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+use crate::models::Polarity;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    /// Bright colours for dark terminal backgrounds
+    #[default]
+    Dark,
+    /// Darker colours for light terminal backgrounds
+    Light,
+    /// Bold, bright colours with reversed selection
+    HighContrast,
+    /// Blue and orange instead of green and red
+    ColorBlind,
+    /// No colours at all, as with NO_COLOR
+    NoColor,
+}
+
+impl ThemeName {
+    // An explicitly chosen theme wins; otherwise a non-empty NO_COLOR turns
+    // colours off, as described at https://no-color.org.
+    pub fn resolve(name: Option<Self>, no_color: Option<&str>) -> Self {
+        match name {
+            Some(name) => name,
+            None if no_color.is_some_and(|value| !value.is_empty()) => Self::NoColor,
+            None => Self::default(),
+        }
+    }
+
+    pub fn from_env(name: Option<Self>) -> Self {
+        Self::resolve(name, std::env::var("NO_COLOR").ok().as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub title: Color,
+    pub label: Color,
+    pub text: Color,
+    pub hint: Color,
+    pub muted: Color,
+    pub chapter: Color,
+    pub accent: Color,
+    pub good: Color,
+    pub bad: Color,
+    pub partial: Color,
+    pub positive: Color,
+    pub negative: Color,
+    pub neutral: Color,
+    pub selection: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(ThemeName::Dark)
+    }
+}
+
+impl Theme {
+    pub fn new(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self {
+                title: Color::Cyan,
+                label: Color::Yellow,
+                text: Color::White,
+                hint: Color::Gray,
+                muted: Color::DarkGray,
+                chapter: Color::Blue,
+                accent: Color::Magenta,
+                good: Color::Green,
+                bad: Color::Red,
+                partial: Color::Yellow,
+                positive: Color::Green,
+                negative: Color::Red,
+                neutral: Color::Blue,
+                selection: Style::default().bg(Color::DarkGray),
+            },
+            ThemeName::Light => Self {
+                title: Color::Indexed(25),
+                label: Color::Indexed(130),
+                text: Color::Black,
+                hint: Color::Indexed(240),
+                muted: Color::Indexed(246),
+                chapter: Color::Indexed(19),
+                accent: Color::Indexed(90),
+                good: Color::Indexed(28),
+                bad: Color::Indexed(124),
+                partial: Color::Indexed(130),
+                positive: Color::Indexed(28),
+                negative: Color::Indexed(124),
+                neutral: Color::Indexed(19),
+                selection: Style::default().bg(Color::Indexed(252)),
+            },
+            ThemeName::HighContrast => Self {
+                title: Color::LightCyan,
+                label: Color::LightYellow,
+                text: Color::White,
+                hint: Color::White,
+                muted: Color::Gray,
+                chapter: Color::LightBlue,
+                accent: Color::LightMagenta,
+                good: Color::LightGreen,
+                bad: Color::LightRed,
+                partial: Color::LightYellow,
+                positive: Color::LightGreen,
+                negative: Color::LightRed,
+                neutral: Color::LightBlue,
+                selection: Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            },
+            // Okabe-Ito blue, orange and reddish purple, which stay distinct
+            // under the common forms of colour blindness.
+            ThemeName::ColorBlind => Self {
+                title: Color::Indexed(117),
+                label: Color::Indexed(221),
+                text: Color::White,
+                hint: Color::Gray,
+                muted: Color::DarkGray,
+                chapter: Color::Indexed(75),
+                accent: Color::Indexed(175),
+                good: Color::Indexed(32),
+                bad: Color::Indexed(208),
+                partial: Color::Indexed(221),
+                positive: Color::Indexed(32),
+                negative: Color::Indexed(208),
+                neutral: Color::Indexed(175),
+                selection: Style::default().bg(Color::DarkGray),
+            },
+            ThemeName::NoColor => Self {
+                title: Color::Reset,
+                label: Color::Reset,
+                text: Color::Reset,
+                hint: Color::Reset,
+                muted: Color::Reset,
+                chapter: Color::Reset,
+                accent: Color::Reset,
+                good: Color::Reset,
+                bad: Color::Reset,
+                partial: Color::Reset,
+                positive: Color::Reset,
+                negative: Color::Reset,
+                neutral: Color::Reset,
+                selection: Style::default().add_modifier(Modifier::REVERSED),
+            },
+        }
+    }
+
+    pub fn polarity(&self, polarity: &Polarity) -> Color {
+        match polarity {
+            Polarity::Positive => self.positive,
+            Polarity::Negative => self.negative,
+            Polarity::Neutral => self.neutral,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
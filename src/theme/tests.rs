@@ -0,0 +1,24 @@
+use super::*;
+
+#[test]
+fn no_color_turns_colours_off_unless_a_theme_is_chosen() {
+    assert_eq!(ThemeName::resolve(None, None), ThemeName::Dark);
+    assert_eq!(ThemeName::resolve(None, Some("")), ThemeName::Dark);
+    assert_eq!(ThemeName::resolve(None, Some("1")), ThemeName::NoColor);
+    assert_eq!(ThemeName::resolve(Some(ThemeName::Light), Some("1")), ThemeName::Light);
+}
+
+#[test]
+fn color_blind_polarities_avoid_red_and_green() {
+    let theme = Theme::new(ThemeName::ColorBlind);
+    for polarity in &Polarity::ALL {
+        assert!(![Color::Red, Color::Green, Color::LightRed, Color::LightGreen].contains(&theme.polarity(polarity)));
+    }
+    assert_ne!(theme.positive, theme.negative);
+}
+
+#[test]
+fn theme_names_parse_from_the_command_line_and_config() {
+    assert_eq!(ThemeName::from_str("high-contrast", false), Ok(ThemeName::HighContrast));
+    assert_eq!(serde_json::from_str::<ThemeName>("\"color-blind\"").unwrap(), ThemeName::ColorBlind);
+}
@@ -0,0 +1,93 @@
+use crate::VALID_TITLES;
+
+// Common abbreviations and stylized names, and the edition they imply if any.
+// Ids and stylized names in `VALID_TITLES` match without being listed here.
+const ALIASES: &[(&str, &str, Option<&str>)] = &[
+    ("ch", "chaos_head", None),
+    ("noah", "chaos_head", Some("noah")),
+    ("chn", "chaos_head", Some("noah")),
+    ("sg", "steins_gate", None),
+    ("Steins;Gate", "steins_gate", None),
+    ("rn", "robotics_notes", None),
+    ("Robotics;Notes", "robotics_notes", None),
+    ("cc", "chaos_child", None),
+    ("Chaos;Child", "chaos_child", None),
+    ("o9", "occultic_nine", None),
+    ("Occultic;Nine", "occultic_nine", None),
+    ("ac", "anonymous_code", None),
+    ("Anonymous;Code", "anonymous_code", None),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TitleLookup {
+    Found { title: &'static str, edition: Option<&'static str> },
+    Ambiguous(Vec<&'static str>),
+    NotFound { suggestion: Option<&'static str> },
+}
+
+// "Chaos;Head", "chaos-head" and "chaoshead" all become "chaoshead".
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+fn names() -> Vec<(String, &'static str, Option<&'static str>)> {
+    let mut names: Vec<_> = VALID_TITLES.iter()
+        .flat_map(|(&title, stylized)| {
+            std::iter::once((normalize(title), title, None))
+                .chain(stylized.map(|stylized| (normalize(stylized), title, None)))
+        })
+        .chain(ALIASES.iter().map(|&(alias, title, edition)| (normalize(alias), title, edition)))
+        .collect();
+    names.sort();
+    names
+}
+
+// Tries, in order: the exact id, an alias or stylized name ignoring case and
+// punctuation, then a prefix that only one title starts with. Anything else
+// gets the closest name as a suggestion.
+pub fn find_title(query: &str) -> TitleLookup {
+    if let Some((&title, _)) = VALID_TITLES.get_key_value(query) {
+        return TitleLookup::Found { title, edition: None };
+    }
+
+    let query = normalize(query);
+    let names = names();
+    if query.is_empty() {
+        return TitleLookup::NotFound { suggestion: None };
+    }
+    if let Some(&(_, title, edition)) = names.iter().find(|(name, ..)| *name == query) {
+        return TitleLookup::Found { title, edition };
+    }
+
+    let mut prefixed: Vec<&'static str> = names.iter()
+        .filter(|(name, ..)| name.starts_with(&query))
+        .map(|&(_, title, _)| title)
+        .collect();
+    prefixed.sort();
+    prefixed.dedup();
+    match prefixed.as_slice() {
+        [title] => return TitleLookup::Found { title, edition: None },
+        [_, ..] => return TitleLookup::Ambiguous(prefixed),
+        [] => {}
+    }
+
+    let suggestion = names.iter()
+        .map(|(name, title, _)| (strsim::jaro_winkler(name, &query), *title))
+        .filter(|(score, _)| *score >= 0.8)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, title)| title);
+    TitleLookup::NotFound { suggestion }
+}
+
+// Titles with bundled route data, for "Available titles" hints.
+pub fn available_titles() -> Vec<&'static str> {
+    let mut titles: Vec<&'static str> = VALID_TITLES.keys()
+        .copied()
+        .filter(|title| crate::data::load_title(title).is_some())
+        .collect();
+    titles.sort();
+    titles
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,40 @@
+use super::*;
+
+fn found(title: &'static str) -> TitleLookup {
+    TitleLookup::Found { title, edition: None }
+}
+
+#[test]
+fn ids_stylized_names_and_aliases_resolve() {
+    assert_eq!(find_title("chaos_head"), found("chaos_head"));
+    assert_eq!(find_title("Chaos;Head"), found("chaos_head"));
+    assert_eq!(find_title("chaoshead"), found("chaos_head"));
+    assert_eq!(find_title("CH"), found("chaos_head"));
+    assert_eq!(find_title("Steins;Gate"), found("steins_gate"));
+    assert_eq!(find_title("sg"), found("steins_gate"));
+}
+
+#[test]
+fn edition_aliases_pick_the_edition() {
+    assert_eq!(find_title("noah"), TitleLookup::Found { title: "chaos_head", edition: Some("noah") });
+}
+
+#[test]
+fn unique_prefixes_resolve_and_shared_ones_are_ambiguous() {
+    assert_eq!(find_title("robo"), found("robotics_notes"));
+    assert_eq!(find_title("chaos"), TitleLookup::Ambiguous(vec!["chaos_child", "chaos_head"]));
+    assert_eq!(find_title("chaos_"), TitleLookup::Ambiguous(vec!["chaos_child", "chaos_head"]));
+}
+
+#[test]
+fn typos_suggest_the_closest_title() {
+    assert_eq!(find_title("chaos_haed"), TitleLookup::NotFound { suggestion: Some("chaos_head") });
+    assert_eq!(find_title("occultc nine"), TitleLookup::NotFound { suggestion: Some("occultic_nine") });
+    assert_eq!(find_title("zzz"), TitleLookup::NotFound { suggestion: None });
+    assert_eq!(find_title(";;"), TitleLookup::NotFound { suggestion: None });
+}
+
+#[test]
+fn only_titles_with_data_are_available() {
+    assert_eq!(available_titles(), ["chaos_head"]);
+}
@@ -1,3 +1,4 @@
+use clap::ValueEnum;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
     execute,
@@ -6,26 +7,76 @@ use crossterm::{
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
     widgets::{
-        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap,
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs, Wrap,
     },
     Frame, Terminal,
 };
+use std::collections::HashSet;
 use std::io;
+use std::time::Duration;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use crate::models::*;
+use crate::storage::{ProgressStore, SavedProgress};
+use crate::glyphs::{GlyphMode, Glyphs};
+use crate::theme::{Theme, ThemeName};
+
+const HIDDEN_TEXT: &str = "???";
+const WIDE_LAYOUT_WIDTH: u16 = 150;
+const TABS: [&str; 4] = ["Routes", "Achievements", "TIPS", "Collection"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Keymap {
+    /// Arrow keys to move
+    #[default]
+    Default,
+    /// Also j and k to move down and up
+    Vim,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Preferences {
+    pub spoilers: SpoilerLevel,
+    pub theme: ThemeName,
+    pub glyphs: GlyphMode,
+    pub keymap: Keymap,
+    pub collapse_completed: bool,
+}
 
 pub struct App {
     pub game_data: GameData,
+    pub spoilers: SpoilerLevel,
+    pub theme: Theme,
+    pub glyphs: Glyphs,
+    pub keymap: Keymap,
+    pub store: Option<ProgressStore>,
+    pub synced: Option<String>,
+    pub base: SavedProgress,
+    pub unsaved_changes: bool,
     pub current_view: View,
     pub route_list_state: ListState,
     pub step_list_state: ListState,
+    pub achievement_list_state: ListState,
+    pub tip_list_state: ListState,
+    pub tip_chapter: Option<u32>,
+    pub collection_list_state: ListState,
+    pub missing_only: bool,
     pub should_quit: bool,
     pub show_help: bool,
-    pub step_indices: Vec<(usize, usize)>,
-    pub display_to_step_mapping: Vec<Option<usize>>,
+    pub input: Option<TextInput>,
+    pub rows: Vec<Row>,
+    pub collapsed: HashSet<usize>,
+    pub collapse_completed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Row {
+    Chapter { chapter_idx: usize },
+    Step { chapter_idx: usize, step_idx: usize },
 }
 
 #[derive(Debug, Clone)]
@@ -33,33 +84,79 @@ pub enum View {
     RouteSelection,
     RouteDetails { route_idx: usize },
     StepDetails { route_idx: usize, chapter_idx: usize, step_idx: usize },
+    Achievements,
+    Tips,
+    Collection,
+}
+
+#[derive(Debug, Clone)]
+pub enum InputTarget {
+    Note { step_id: String },
+    SaveSlot { step_id: String },
+    Choice { step_id: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    pub target: InputTarget,
+    pub buffer: String,
 }
 
 impl App {
-    pub fn new(game_data: GameData) -> Self {
+    pub fn new(game_data: GameData, spoilers: SpoilerLevel) -> Self {
         let mut route_list_state = ListState::default();
         route_list_state.select(Some(0));
+        let mut achievement_list_state = ListState::default();
+        achievement_list_state.select(Some(0));
+        let mut tip_list_state = ListState::default();
+        tip_list_state.select(Some(0));
+        let mut collection_list_state = ListState::default();
+        collection_list_state.select(Some(0));
 
-        Self {
+        let mut app = Self {
             game_data,
+            spoilers,
+            theme: Theme::default(),
+            glyphs: Glyphs::default(),
+            keymap: Keymap::default(),
+            store: None,
+            synced: None,
+            base: SavedProgress::default(),
+            unsaved_changes: false,
             current_view: View::RouteSelection,
             route_list_state,
             step_list_state: ListState::default(),
+            achievement_list_state,
+            tip_list_state,
+            tip_chapter: None,
+            collection_list_state,
+            missing_only: false,
             should_quit: false,
             show_help: false,
-            step_indices: Vec::new(),
-            display_to_step_mapping: Vec::new(),
-        }
+            input: None,
+            rows: Vec::new(),
+            collapsed: HashSet::new(),
+            collapse_completed: false,
+        };
+        app.preview_route();
+        app
     }
 
     pub fn run<B: Backend>(mut self, terminal: &mut Terminal<B>) -> Result<()> {
         loop {
+            self.sync_from_disk()?;
             terminal.draw(|f| self.draw(f))?;
 
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    self.handle_key_event(key.code);
-                }
+            if event::poll(Duration::from_millis(500))?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.sync_from_disk()?;
+                self.handle_key_event(key.code);
+            }
+
+            if self.unsaved_changes {
+                self.save()?;
             }
 
             if self.should_quit {
@@ -69,7 +166,56 @@ impl App {
         Ok(())
     }
 
+    // `serve` can write progress while the TUI is open, so pick up its changes
+    // before acting on a key rather than overwriting them on the next save.
+    fn sync_from_disk(&mut self) -> Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let contents = store.read()?;
+        if contents != self.synced {
+            let saved = contents.as_deref().map(|c| store.parse(c)).transpose()?.unwrap_or_default();
+            self.game_data.clear_progress();
+            saved.apply(&mut self.game_data);
+            self.base = SavedProgress::from_game(&self.game_data);
+            self.synced = contents;
+            self.preview_route();
+        }
+        Ok(())
+    }
+
+    // Something may have been written between the last sync and this save,
+    // so replay this session's edits on top of it rather than overwrite it.
+    fn save(&mut self) -> Result<()> {
+        if let Some(store) = &self.store {
+            let contents = store.read()?;
+            if contents != self.synced {
+                let theirs = contents.as_deref().map(|c| store.parse(c)).transpose()?.unwrap_or_default();
+                let merged = theirs.rebase(&self.base, SavedProgress::from_game(&self.game_data));
+                self.game_data.clear_progress();
+                merged.apply(&mut self.game_data);
+            }
+            self.synced = Some(store.save(&self.game_data)?);
+            self.base = SavedProgress::from_game(&self.game_data);
+        }
+        self.unsaved_changes = false;
+        Ok(())
+    }
+
     fn handle_key_event(&mut self, key: KeyCode) {
+        if let Some(input) = &mut self.input {
+            match key {
+                KeyCode::Enter => self.submit_input(),
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    input.buffer.pop();
+                }
+                KeyCode::Char(c) => input.buffer.push(c),
+                _ => {}
+            }
+            return;
+        }
+
         if self.show_help {
             if matches!(key, KeyCode::Char('h') | KeyCode::Esc) {
                 self.show_help = false;
@@ -77,29 +223,76 @@ impl App {
             return;
         }
 
+        let key = match (self.keymap, key) {
+            (Keymap::Vim, KeyCode::Char('j')) => KeyCode::Down,
+            (Keymap::Vim, KeyCode::Char('k')) => KeyCode::Up,
+            _ => key,
+        };
+
         match key {
             KeyCode::Char('q') => self.should_quit = true,
             KeyCode::Char('h') => self.show_help = true,
             KeyCode::Esc => self.go_back(),
             KeyCode::Enter => self.select_current(),
+            KeyCode::Tab => self.next_tab(),
             KeyCode::Up => self.move_up(),
             KeyCode::Down => self.move_down(),
+            KeyCode::Left if matches!(self.current_view, View::Tips) => self.cycle_tip_chapter(false),
+            KeyCode::Right if matches!(self.current_view, View::Tips) => self.cycle_tip_chapter(true),
+            KeyCode::Char(' ') if matches!(self.current_view, View::Achievements) => self.toggle_achievement(),
+            KeyCode::Char(' ') if matches!(self.current_view, View::Tips) => self.toggle_tip(),
+            KeyCode::Char('m') if matches!(self.current_view, View::Collection) => {
+                self.missing_only = !self.missing_only;
+                self.collection_list_state.select(Some(0));
+            }
             KeyCode::Char(' ') => self.toggle_step_completion(),
+            KeyCode::Char('x') => self.toggle_step_skipped(),
+            KeyCode::Char('n') => self.edit_note(),
+            KeyCode::Char('s') => self.edit_save_slot(),
+            KeyCode::Char('c') => self.edit_choice(),
+            KeyCode::Char('z') => {
+                if let Some(Row::Chapter { chapter_idx } | Row::Step { chapter_idx, .. }) = self.selected_row() {
+                    self.toggle_chapter(chapter_idx);
+                }
+            }
             _ => {}
         }
     }
 
+    fn tab_index(&self) -> usize {
+        match self.current_view {
+            View::Achievements => 1,
+            View::Tips => 2,
+            View::Collection => 3,
+            _ => 0,
+        }
+    }
+
+    // Tabs switch between the top-level lists; inside a route, Esc comes first.
+    fn next_tab(&mut self) {
+        self.current_view = match self.current_view {
+            View::RouteSelection => View::Achievements,
+            View::Achievements => View::Tips,
+            View::Tips => View::Collection,
+            View::Collection => View::RouteSelection,
+            _ => return,
+        };
+        self.preview_route();
+    }
+
     fn go_back(&mut self) {
         match &self.current_view {
             View::RouteSelection => {}
+            View::Achievements | View::Tips | View::Collection => {
+                self.current_view = View::RouteSelection;
+                self.preview_route();
+            }
             View::RouteDetails { .. } => {
                 self.current_view = View::RouteSelection;
-                self.step_indices.clear();
-                self.display_to_step_mapping.clear();
+                self.preview_route();
             }
             View::StepDetails { route_idx, .. } => {
                 self.current_view = View::RouteDetails { route_idx: *route_idx };
-                self.step_list_state = ListState::default();
             }
         }
     }
@@ -107,32 +300,25 @@ impl App {
     fn select_current(&mut self) {
         match &self.current_view {
             View::RouteSelection => {
-                if let Some(selected) = self.route_list_state.selected() {
-                    if selected < self.game_data.routes.len() {
-                        self.current_view = View::RouteDetails { route_idx: selected };
-                        self.game_data.current_route = Some(selected);
-                        self.build_step_display_mapping(selected);
-                        self.step_list_state = ListState::default();
-                        if let Some(first_selectable) = self.display_to_step_mapping.iter().position(|x| x.is_some()) {
-                            self.step_list_state.select(Some(first_selectable));
-                        }
-                    }
+                if let Some(selected) = self.route_list_state.selected()
+                    && selected < self.game_data.routes.len()
+                {
+                    self.current_view = View::RouteDetails { route_idx: selected };
+                    self.game_data.current_route = Some(selected);
+                    self.load_route(selected);
+                    self.step_list_state = ListState::default();
+                    let first_step = self.rows.iter().position(|row| matches!(row, Row::Step { .. }));
+                    self.step_list_state.select(first_step.or(Some(0)));
                 }
             }
-            View::RouteDetails { route_idx } => {
-                if let Some(selected_display_idx) = self.step_list_state.selected() {
-                    if let Some(Some(step_idx)) = self.display_to_step_mapping.get(selected_display_idx) {
-                        if let Some((chapter_idx, step_idx_in_chapter)) = self.step_indices.get(*step_idx) {
-                            self.current_view = View::StepDetails {
-                                route_idx: *route_idx,
-                                chapter_idx: *chapter_idx,
-                                step_idx: *step_idx_in_chapter,
-                            };
-                        }
-                    }
+            View::RouteDetails { route_idx } => match self.selected_row() {
+                Some(Row::Step { chapter_idx, step_idx }) => {
+                    self.current_view = View::StepDetails { route_idx: *route_idx, chapter_idx, step_idx };
                 }
-            }
-            View::StepDetails { .. } => {}
+                Some(Row::Chapter { chapter_idx }) => self.toggle_chapter(chapter_idx),
+                None => {}
+            },
+            View::StepDetails { .. } | View::Achievements | View::Tips | View::Collection => {}
         }
     }
 
@@ -142,25 +328,28 @@ impl App {
                 let selected = self.route_list_state.selected().unwrap_or(0);
                 if selected > 0 {
                     self.route_list_state.select(Some(selected - 1));
+                    self.preview_route();
                 }
             }
             View::RouteDetails { route_idx: _ } => {
-                let selected = self.step_list_state.selected().unwrap_or(0);
-                if selected > 0 {
-                    for i in (0..selected).rev() {
-                        if self.display_to_step_mapping.get(i).and_then(|x| x.as_ref()).is_some() {
-                            self.step_list_state.select(Some(i));
-                            return;
-                        }
-                    }
-                } else {
-                    for i in (0..self.display_to_step_mapping.len()).rev() {
-                        if self.display_to_step_mapping.get(i).and_then(|x| x.as_ref()).is_some() {
-                            self.step_list_state.select(Some(i));
-                            return;
-                        }
-                    }
+                if self.rows.is_empty() {
+                    return;
                 }
+                let selected = self.step_list_state.selected().unwrap_or(0);
+                let previous = if selected == 0 { self.rows.len() - 1 } else { selected - 1 };
+                self.step_list_state.select(Some(previous));
+            }
+            View::Achievements => {
+                let selected = self.achievement_list_state.selected().unwrap_or(0);
+                self.achievement_list_state.select(Some(selected.saturating_sub(1)));
+            }
+            View::Tips => {
+                let selected = self.tip_list_state.selected().unwrap_or(0);
+                self.tip_list_state.select(Some(selected.saturating_sub(1)));
+            }
+            View::Collection => {
+                let selected = self.collection_list_state.selected().unwrap_or(0);
+                self.collection_list_state.select(Some(selected.saturating_sub(1)));
             }
             View::StepDetails { .. } => {}
         }
@@ -172,80 +361,245 @@ impl App {
                 let selected = self.route_list_state.selected().unwrap_or(0);
                 if selected < self.game_data.routes.len().saturating_sub(1) {
                     self.route_list_state.select(Some(selected + 1));
+                    self.preview_route();
                 }
             }
             View::RouteDetails { route_idx: _ } => {
-                let selected = self.step_list_state.selected().unwrap_or(0);
-                let total_items = self.display_to_step_mapping.len();
-                
-                for i in (selected + 1)..total_items {
-                    if self.display_to_step_mapping.get(i).and_then(|x| x.as_ref()).is_some() {
-                        self.step_list_state.select(Some(i));
-                        return;
-                    }
-                }
-                
-                for i in 0..=selected {
-                    if self.display_to_step_mapping.get(i).and_then(|x| x.as_ref()).is_some() {
-                        self.step_list_state.select(Some(i));
-                        return;
-                    }
+                if self.rows.is_empty() {
+                    return;
                 }
+                let next = self.step_list_state.selected().map_or(0, |selected| (selected + 1) % self.rows.len());
+                self.step_list_state.select(Some(next));
+            }
+            View::Achievements => {
+                let selected = self.achievement_list_state.selected().unwrap_or(0);
+                let last = self.game_data.achievements.len().saturating_sub(1);
+                self.achievement_list_state.select(Some((selected + 1).min(last)));
+            }
+            View::Tips => {
+                let selected = self.tip_list_state.selected().unwrap_or(0);
+                let last = self.visible_tips().len().saturating_sub(1);
+                self.tip_list_state.select(Some((selected + 1).min(last)));
+            }
+            View::Collection => {
+                let selected = self.collection_list_state.selected().unwrap_or(0);
+                let last = self.visible_collection().len().saturating_sub(1);
+                self.collection_list_state.select(Some((selected + 1).min(last)));
             }
             View::StepDetails { .. } => {}
         }
     }
 
     fn toggle_step_completion(&mut self) {
-        if let View::RouteDetails { route_idx } = &self.current_view {
-            if let Some(selected_display_idx) = self.step_list_state.selected() {
-                if let Some(Some(step_idx)) = self.display_to_step_mapping.get(selected_display_idx) {
-                    if let Some((chapter_idx, step_idx_in_chapter)) = self.step_indices.get(*step_idx) {
-                        let route_idx = *route_idx;
-                        let chapter_idx = *chapter_idx;
-                        let step_idx_in_chapter = *step_idx_in_chapter;
-                        
-                        let (step_id, new_completion) = if let Some(route) = self.game_data.routes.get(route_idx) {
-                            if let Some(chapter) = route.chapters.get(chapter_idx) {
-                                if let Some(step) = chapter.steps.get(step_idx_in_chapter) {
-                                    (step.id.clone(), !step.completed)
-                                } else {
-                                    return;
-                                }
-                            } else {
-                                return;
-                            }
-                        } else {
-                            return;
-                        };
-                        
-                        if let Some(route) = self.game_data.routes.get_mut(route_idx) {
-                            if let Some(chapter) = route.chapters.get_mut(chapter_idx) {
-                                if let Some(step) = chapter.steps.get_mut(step_idx_in_chapter) {
-                                    step.completed = new_completion;
-                                }
-                            }
-                        }
-                        
-                        self.game_data.mark_step_completed(&step_id, new_completion);
-                    }
-                }
+        if let View::RouteDetails { route_idx } = self.current_view
+            && let Some(Row::Step { chapter_idx, step_idx }) = self.selected_row()
+            && let Some(step) = self.game_data.routes.get(route_idx)
+                .and_then(|route| route.chapters.get(chapter_idx))
+                .and_then(|chapter| chapter.steps.get(step_idx))
+        {
+            let step_id = step.id.clone();
+            let is_checkpoint = matches!(step.step_type, StepType::Checkpoint { .. });
+
+            if self.game_data.toggle_step(&step_id) == Some(true) && is_checkpoint {
+                self.prompt_save_slot(step_id);
+            }
+            self.unsaved_changes = true;
+
+            if self.collapse_completed
+                && self.is_chapter_complete(route_idx, chapter_idx)
+                && !self.collapsed.contains(&chapter_idx)
+            {
+                self.toggle_chapter(chapter_idx);
+            }
+        }
+    }
+
+    // Achievements earned through their step or route can't be untoggled here;
+    // the step or route has to be undone instead.
+    fn toggle_achievement(&mut self) {
+        let Some(achievement) = self.achievement_list_state.selected()
+            .and_then(|idx| self.game_data.achievements.get(idx))
+        else {
+            return;
+        };
+        if achievement.unlock.as_ref().is_some_and(|unlock| self.game_data.is_unlocked(unlock)) {
+            return;
+        }
+
+        let id = achievement.id.clone();
+        self.game_data.toggle_achievement(&id);
+        self.unsaved_changes = true;
+    }
+
+    fn visible_tips(&self) -> Vec<&Tip> {
+        self.game_data.tips.iter()
+            .filter(|tip| self.tip_chapter.is_none_or(|chapter| tip.chapter == chapter))
+            .collect()
+    }
+
+    fn visible_collection(&self) -> Vec<&CollectionItem> {
+        self.game_data.collection.iter()
+            .filter(|item| !self.missing_only || !self.game_data.is_unlocked(&item.unlock))
+            .collect()
+    }
+
+    // Steps through all chapters, then each chapter that has TIPS, and round
+    // again.
+    fn cycle_tip_chapter(&mut self, forward: bool) {
+        let mut filters: Vec<Option<u32>> = vec![None];
+        filters.extend(self.game_data.tip_chapters().into_iter().map(Some));
+        let current = filters.iter().position(|filter| *filter == self.tip_chapter).unwrap_or(0);
+        let next = if forward {
+            (current + 1) % filters.len()
+        } else {
+            (current + filters.len() - 1) % filters.len()
+        };
+        self.tip_chapter = filters[next];
+        self.tip_list_state.select(Some(0));
+    }
+
+    fn toggle_tip(&mut self) {
+        let Some(id) = self.tip_list_state.selected()
+            .and_then(|idx| self.visible_tips().get(idx).map(|tip| tip.id.clone()))
+        else {
+            return;
+        };
+        self.game_data.toggle_tip(&id);
+        self.unsaved_changes = true;
+    }
+
+    fn toggle_step_skipped(&mut self) {
+        let step_id = match (&self.current_view, self.selected_row()) {
+            (View::RouteDetails { route_idx }, Some(Row::Step { chapter_idx, step_idx })) => self.game_data.routes
+                .get(*route_idx)
+                .and_then(|route| route.chapters.get(chapter_idx))
+                .and_then(|chapter| chapter.steps.get(step_idx))
+                .map(|step| step.id.clone()),
+            (View::StepDetails { .. }, _) => self.viewed_step().map(|step| step.id.clone()),
+            _ => None,
+        };
+
+        if let Some(step_id) = step_id {
+            self.game_data.toggle_skipped(&step_id);
+            self.unsaved_changes = true;
+        }
+    }
+
+    fn selected_row(&self) -> Option<Row> {
+        if !matches!(self.current_view, View::RouteDetails { .. }) {
+            return None;
+        }
+        self.rows.get(self.step_list_state.selected()?).copied()
+    }
+
+    fn is_chapter_complete(&self, route_idx: usize, chapter_idx: usize) -> bool {
+        let (done, total) = self.game_data.chapter_progress(route_idx, chapter_idx);
+        total > 0 && done == total
+    }
+
+    fn toggle_chapter(&mut self, chapter_idx: usize) {
+        let View::RouteDetails { route_idx } = self.current_view else {
+            return;
+        };
+        if !self.collapsed.remove(&chapter_idx) {
+            self.collapsed.insert(chapter_idx);
+        }
+        self.build_rows(route_idx);
+        let header = self.rows.iter().position(|row| *row == Row::Chapter { chapter_idx });
+        self.step_list_state.select(header);
+    }
+
+    fn viewed_step(&self) -> Option<&Step> {
+        let View::StepDetails { route_idx, chapter_idx, step_idx } = &self.current_view else {
+            return None;
+        };
+        self.game_data.routes.get(*route_idx)
+            .and_then(|route| route.chapters.get(*chapter_idx))
+            .and_then(|chapter| chapter.steps.get(*step_idx))
+    }
+
+    fn edit_note(&mut self) {
+        if let Some(step) = self.viewed_step() {
+            self.input = Some(TextInput {
+                buffer: self.game_data.note(&step.id).unwrap_or_default().to_string(),
+                target: InputTarget::Note { step_id: step.id.clone() },
+            });
+        }
+    }
+
+    fn edit_save_slot(&mut self) {
+        if let Some(step) = self.viewed_step()
+            && matches!(step.step_type, StepType::Checkpoint { .. })
+        {
+            self.prompt_save_slot(step.id.clone());
+        }
+    }
+
+    fn edit_choice(&mut self) {
+        if let Some(step) = self.viewed_step()
+            && !step.choice_options().is_empty()
+        {
+            self.input = Some(TextInput {
+                buffer: self.game_data.choice(&step.id).map(format_choice).unwrap_or_default(),
+                target: InputTarget::Choice { step_id: step.id.clone() },
+            });
+        }
+    }
+
+    fn prompt_save_slot(&mut self, step_id: String) {
+        self.input = Some(TextInput {
+            buffer: self.game_data.save_slot(&step_id).unwrap_or_default().to_string(),
+            target: InputTarget::SaveSlot { step_id },
+        });
+    }
+
+    fn submit_input(&mut self) {
+        let Some(input) = self.input.take() else {
+            return;
+        };
+
+        match input.target {
+            InputTarget::Note { step_id } => self.game_data.set_note(&step_id, &input.buffer),
+            InputTarget::SaveSlot { step_id } => self.game_data.set_save_slot(&step_id, &input.buffer),
+            InputTarget::Choice { ref step_id } => {
+                let Some(choice) = self.game_data.step(step_id).and_then(|step| parse_choice(step, &input.buffer)) else {
+                    self.input = Some(input);
+                    return;
+                };
+                self.game_data.record_choice(step_id, choice);
             }
         }
+        self.unsaved_changes = true;
+    }
+
+    // The wide layout shows the highlighted route's steps next to the route
+    // list, so keep its rows loaded whenever the highlight moves.
+    fn preview_route(&mut self) {
+        if matches!(self.current_view, View::RouteSelection)
+            && let Some(route_idx) = self.route_list_state.selected()
+        {
+            self.load_route(route_idx);
+            self.step_list_state.select(None);
+        }
     }
 
-    fn build_step_display_mapping(&mut self, route_idx: usize) {
-        self.step_indices.clear();
-        self.display_to_step_mapping.clear();
-        
+    fn load_route(&mut self, route_idx: usize) {
+        let chapters = self.game_data.routes.get(route_idx).map_or(0, |route| route.chapters.len());
+        self.collapsed = (0..chapters)
+            .filter(|&idx| self.collapse_completed && self.is_chapter_complete(route_idx, idx))
+            .collect();
+        self.build_rows(route_idx);
+    }
+
+    fn build_rows(&mut self, route_idx: usize) {
+        self.rows.clear();
+
         if let Some(route) = self.game_data.routes.get(route_idx) {
             for (chapter_idx, chapter) in route.chapters.iter().enumerate() {
-                self.display_to_step_mapping.push(None);
-                
-                for (step_idx, _) in chapter.steps.iter().enumerate() {
-                    let step_indices_idx = self.step_indices.len();
-                    self.step_indices.push((chapter_idx, step_idx));
-                    self.display_to_step_mapping.push(Some(step_indices_idx));
+                self.rows.push(Row::Chapter { chapter_idx });
+
+                if !self.collapsed.contains(&chapter_idx) {
+                    self.rows.extend((0..chapter.steps.len()).map(|step_idx| Row::Step { chapter_idx, step_idx }));
                 }
             }
         }
@@ -254,64 +608,175 @@ impl App {
     fn draw(&mut self, f: &mut Frame) {
         let size = f.area();
 
-        match &self.current_view {
-            View::RouteSelection => self.draw_route_selection(f, size),
-            View::RouteDetails { route_idx } => self.draw_route_details(f, size, *route_idx),
-            View::StepDetails { route_idx, chapter_idx, step_idx } => {
-                self.draw_step_details(f, size, *route_idx, *chapter_idx, *step_idx)
+        if matches!(self.current_view, View::Achievements) {
+            self.draw_achievements(f, size);
+        } else if matches!(self.current_view, View::Tips) {
+            self.draw_tips(f, size);
+        } else if matches!(self.current_view, View::Collection) {
+            self.draw_collection(f, size);
+        } else if size.width >= WIDE_LAYOUT_WIDTH {
+            self.draw_wide(f, size);
+        } else {
+            match &self.current_view {
+                View::RouteSelection => self.draw_route_selection(f, size, true),
+                View::Achievements | View::Tips | View::Collection => {}
+                View::RouteDetails { route_idx } => self.draw_route_details(f, size, *route_idx, true),
+                View::StepDetails { route_idx, chapter_idx, step_idx } => {
+                    self.draw_step_details(f, size, *route_idx, *chapter_idx, *step_idx, true)
+                }
             }
         }
 
+        match &self.input {
+            Some(TextInput { target: InputTarget::SaveSlot { step_id }, buffer }) => {
+                self.draw_save_slot_popup(f, size, step_id, buffer);
+            }
+            Some(TextInput { target: InputTarget::Choice { step_id }, buffer }) => {
+                self.draw_choice_popup(f, size, step_id, buffer);
+            }
+            _ => {}
+        }
+
         if self.show_help {
             self.draw_help_popup(f, size);
         }
     }
 
-    fn draw_route_selection(&mut self, f: &mut Frame, area: Rect) {
+    // Routes, steps and step details side by side, with one line of controls
+    // for whichever pane has focus.
+    fn draw_wide(&mut self, f: &mut Frame, area: Rect) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+        self.draw_tabs(f, chunks[0]);
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Length(3),
+                Constraint::Percentage(25),
+                Constraint::Percentage(35),
+                Constraint::Percentage(40),
+            ])
+            .split(chunks[1]);
+
+        self.draw_route_selection(f, panes[0], false);
+
+        let (route_idx, step) = match self.current_view {
+            View::RouteSelection => (self.route_list_state.selected(), None),
+            View::RouteDetails { route_idx } => match self.selected_row() {
+                Some(Row::Step { chapter_idx, step_idx }) => (Some(route_idx), Some((chapter_idx, step_idx))),
+                _ => (Some(route_idx), None),
+            },
+            View::StepDetails { route_idx, chapter_idx, step_idx } => (Some(route_idx), Some((chapter_idx, step_idx))),
+            View::Achievements | View::Tips | View::Collection => (None, None),
+        };
+
+        if let Some(route_idx) = route_idx {
+            self.draw_route_details(f, panes[1], route_idx, false);
+        }
+
+        match (route_idx, step) {
+            (Some(route_idx), Some((chapter_idx, step_idx))) => {
+                self.draw_step_details(f, panes[2], route_idx, chapter_idx, step_idx, false);
+            }
+            _ => {
+                let placeholder = Paragraph::new(Text::styled(
+                    "Select a step to see its details here.",
+                    Style::default().fg(self.theme.muted),
+                ))
+                .block(Block::default().title("Details").borders(Borders::ALL));
+                f.render_widget(placeholder, panes[2]);
+            }
+        }
+
+        let arrows = self.glyphs.arrows;
+        let controls = match &self.current_view {
+            View::RouteSelection => format!("{arrows}: Navigate | Enter: Select | Tab: Switch | h: Help | q: Quit"),
+            View::RouteDetails { .. } => {
+                format!("{arrows}: Move | Enter: Open | Esc: Back | Space: Toggle | x: Skip | z: Fold | h: Help | q: Quit")
+            }
+            View::StepDetails { .. } => match (&self.input, self.viewed_step()) {
+                (Some(TextInput { target: InputTarget::Note { .. }, .. }), _) => "Enter: Save note | Esc: Cancel".to_string(),
+                (_, Some(step)) => step_controls(step).to_string(),
+                (_, None) => String::new(),
+            },
+            View::Achievements | View::Tips | View::Collection => String::new(),
+        };
+        let help_text = Paragraph::new(Line::from(vec![
+            Span::styled("Controls: ", Style::default().fg(self.theme.label).add_modifier(Modifier::BOLD)),
+            Span::raw(controls),
+        ]))
+        .style(Style::default().fg(self.theme.hint))
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(help_text, chunks[2]);
+    }
+
+    fn draw_tabs(&self, f: &mut Frame, area: Rect) {
+        let tabs = Tabs::new(TABS)
+            .select(self.tab_index())
+            .style(Style::default().fg(self.theme.hint))
+            .highlight_style(Style::default().fg(self.theme.title).add_modifier(Modifier::BOLD | Modifier::UNDERLINED))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(
+                        self.game_data.guide_title(),
+                        Style::default().fg(self.theme.title).add_modifier(Modifier::BOLD),
+                    ))
+                    .title_top(Line::from(format!("{:.0}% complete", self.game_data.title_completion())).right_aligned()),
+            );
+        f.render_widget(tabs, area);
+    }
+
+    fn draw_route_selection(&mut self, f: &mut Frame, area: Rect, controls: bool) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(if controls { 3 } else { 0 }),
                 Constraint::Min(0),
-                Constraint::Length(2),
+                Constraint::Length(if controls { 3 } else { 0 }),
             ])
             .split(area);
 
-        let title = Paragraph::new("Chaos;Head NoAH - Route Guide")
-            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
-            .block(Block::default().borders(Borders::ALL));
-        f.render_widget(title, chunks[0]);
+        if controls {
+            self.draw_tabs(f, chunks[0]);
+        }
 
         let routes: Vec<ListItem> = self.game_data.routes
             .iter()
             .enumerate()
             .map(|(i, route)| {
                 let completion = self.game_data.get_completion_percentage(i);
-                let available = self.is_route_available(i);
+                let available = self.game_data.is_route_available(i);
+                let name = if self.game_data.is_route_hidden(i, self.spoilers) {
+                    HIDDEN_TEXT.to_string()
+                } else {
+                    route.name.clone()
+                };
                 
                 let style = if !available {
-                    Style::default().fg(Color::DarkGray)
+                    Style::default().fg(self.theme.muted)
                 } else if completion >= 100.0 {
-                    Style::default().fg(Color::Green)
+                    Style::default().fg(self.theme.good)
                 } else if completion > 0.0 {
-                    Style::default().fg(Color::Yellow)
+                    Style::default().fg(self.theme.partial)
                 } else {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(self.theme.text)
                 };
 
                 let status_symbol = if !available {
-                    "🔒"
+                    self.glyphs.locked
                 } else if completion >= 100.0 {
-                    "✓"
+                    self.glyphs.done
                 } else if completion > 0.0 {
-                    "◐"
+                    self.glyphs.partial
                 } else {
-                    "○"
+                    self.glyphs.pending
                 };
 
                 ListItem::new(Line::from(vec![
                     Span::raw(format!("{} ", status_symbol)),
-                    Span::styled(route.name.clone(), style),
+                    Span::styled(name, style),
                     Span::raw(format!(" ({:.0}%)", completion)),
                 ]))
             })
@@ -319,104 +784,392 @@ impl App {
 
         let routes_list = List::new(routes)
             .block(Block::default().title("Routes").borders(Borders::ALL))
-            .highlight_style(Style::default().bg(Color::DarkGray))
-            .highlight_symbol("► ");
+            .highlight_style(self.theme.selection)
+            .highlight_symbol(self.glyphs.pointer);
 
         f.render_stateful_widget(routes_list, chunks[1], &mut self.route_list_state);
 
+        if !controls {
+            return;
+        }
+
         let help_text = Paragraph::new(vec![
             Line::from(vec![
-                Span::styled("Controls: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-                Span::raw("↑/↓: Navigate | Enter: Select | h: Help | q: Quit"),
+                Span::styled("Controls: ", Style::default().fg(self.theme.label).add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{}: Navigate | Enter: Select | Tab: Switch | h: Help | q: Quit", self.glyphs.arrows)),
             ]),
         ])
-        .style(Style::default().fg(Color::Gray))
+        .style(Style::default().fg(self.theme.hint))
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(help_text, chunks[2]);
+    }
+
+    fn draw_achievements(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+        self.draw_tabs(f, chunks[0]);
+
+        let (unlocked, total) = self.game_data.achievement_progress();
+        let block = Block::default()
+            .title(format!("Achievements {unlocked}/{total} ({:.0}%)", self.game_data.achievement_percentage()))
+            .borders(Borders::ALL);
+
+        if self.game_data.achievements.is_empty() {
+            let empty = Paragraph::new(Text::styled(
+                "No achievements are listed for this title yet.",
+                Style::default().fg(self.theme.muted),
+            ))
+            .block(block);
+            f.render_widget(empty, chunks[1]);
+        } else {
+            let items: Vec<ListItem> = self.game_data.achievements
+                .iter()
+                .map(|achievement| {
+                    let unlocked = self.game_data.is_achievement_unlocked(achievement);
+                    let (symbol, style) = if unlocked {
+                        (self.glyphs.done, Style::default().fg(self.theme.good))
+                    } else {
+                        (self.glyphs.pending, Style::default().fg(self.theme.text))
+                    };
+
+                    if self.game_data.is_achievement_hidden(achievement, self.spoilers) {
+                        return ListItem::new(vec![
+                            Line::from(vec![Span::raw(format!("{symbol} ")), Span::styled(HIDDEN_TEXT, style)]),
+                            Line::styled("    Hidden achievement", Style::default().fg(self.theme.muted)),
+                        ]);
+                    }
+
+                    let mut lines = vec![
+                        Line::from(vec![Span::raw(format!("{symbol} ")), Span::styled(achievement.name.clone(), style)]),
+                        Line::styled(format!("    {}", achievement.description), Style::default().fg(self.theme.muted)),
+                    ];
+                    if let Some(unlock) = &achievement.unlock {
+                        lines.push(Line::styled(
+                            format!("    {} {}", self.glyphs.then, self.unlock_label(unlock)),
+                            Style::default().fg(self.theme.hint),
+                        ));
+                    }
+                    ListItem::new(lines)
+                })
+                .collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(self.theme.selection)
+                .highlight_symbol(self.glyphs.pointer);
+            f.render_stateful_widget(list, chunks[1], &mut self.achievement_list_state);
+        }
+
+        let help_text = Paragraph::new(Line::from(vec![
+            Span::styled("Controls: ", Style::default().fg(self.theme.label).add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{}: Navigate | Space: Toggle | Tab: Switch | h: Help | q: Quit", self.glyphs.arrows)),
+        ]))
+        .style(Style::default().fg(self.theme.hint))
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(help_text, chunks[2]);
+    }
+
+    fn draw_tips(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+        self.draw_tabs(f, chunks[0]);
+
+        let (read, total) = self.game_data.tip_progress();
+        let percentage = if total == 0 { 0.0 } else { read as f32 / total as f32 * 100.0 };
+        let filter = match self.tip_chapter {
+            Some(chapter) => format!("chapter {chapter}"),
+            None => "all chapters".to_string(),
+        };
+        let block = Block::default()
+            .title(format!("TIPS {read}/{total} ({percentage:.0}%), {filter}"))
+            .borders(Borders::ALL);
+
+        let tips = self.visible_tips();
+        if tips.is_empty() {
+            let message = if self.game_data.tips.is_empty() {
+                "No TIPS are listed for this title yet."
+            } else {
+                "No TIPS unlock in this chapter."
+            };
+            let empty = Paragraph::new(Text::styled(message, Style::default().fg(self.theme.muted))).block(block);
+            f.render_widget(empty, chunks[1]);
+        } else {
+            let items: Vec<ListItem> = tips
+                .iter()
+                .map(|tip| {
+                    let (symbol, style) = if self.game_data.is_tip_read(&tip.id) {
+                        (self.glyphs.done, Style::default().fg(self.theme.good))
+                    } else {
+                        (self.glyphs.pending, Style::default().fg(self.theme.text))
+                    };
+                    let hidden = self.game_data.is_tip_hidden(tip, self.spoilers);
+                    let term = if hidden { HIDDEN_TEXT.to_string() } else { tip.term.clone() };
+                    let mut location = format!("  Chapter {}", tip.chapter);
+                    if !tip.routes.is_empty() && !hidden {
+                        location.push_str(&format!(", {} only", tip.routes.join(" or ")));
+                    }
+
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("{symbol} ")),
+                        Span::styled(term, style),
+                        Span::styled(location, Style::default().fg(self.theme.muted)),
+                    ]))
+                })
+                .collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(self.theme.selection)
+                .highlight_symbol(self.glyphs.pointer);
+            f.render_stateful_widget(list, chunks[1], &mut self.tip_list_state);
+        }
+
+        let help_text = Paragraph::new(Line::from(vec![
+            Span::styled("Controls: ", Style::default().fg(self.theme.label).add_modifier(Modifier::BOLD)),
+            Span::raw(format!(
+                "{}: Move | {}: Chapter | Space: Toggle | Tab: Switch | h: Help",
+                self.glyphs.arrows, self.glyphs.sideways,
+            )),
+        ]))
+        .style(Style::default().fg(self.theme.hint))
         .block(Block::default().borders(Borders::ALL));
         f.render_widget(help_text, chunks[2]);
     }
 
-    fn draw_route_details(&mut self, f: &mut Frame, area: Rect, route_idx: usize) {
+    fn draw_collection(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+        self.draw_tabs(f, chunks[0]);
+
+        let (collected, total) = self.game_data.collection_progress();
+        let percentage = if total == 0 { 0.0 } else { collected as f32 / total as f32 * 100.0 };
+        let mut title = format!("Collection {collected}/{total} ({percentage:.0}%)");
+        if self.missing_only {
+            title.push_str(", missing only");
+        }
+        let block = Block::default().title(title).borders(Borders::ALL);
+
+        let items = self.visible_collection();
+        if items.is_empty() {
+            let message = if self.game_data.collection.is_empty() {
+                "No collection is listed for this title yet."
+            } else {
+                "Nothing is missing."
+            };
+            let empty = Paragraph::new(Text::styled(message, Style::default().fg(self.theme.muted))).block(block);
+            f.render_widget(empty, chunks[1]);
+        } else {
+            let items: Vec<ListItem> = items
+                .iter()
+                .map(|item| {
+                    let collected = self.game_data.is_unlocked(&item.unlock);
+                    let (symbol, style) = if collected {
+                        (self.glyphs.done, Style::default().fg(self.theme.good))
+                    } else {
+                        (self.glyphs.pending, Style::default().fg(self.theme.text))
+                    };
+                    let name = if self.is_unlock_hidden(&item.unlock) {
+                        HIDDEN_TEXT.to_string()
+                    } else {
+                        item.name.clone()
+                    };
+
+                    let mut lines = vec![Line::from(vec![
+                        Span::raw(format!("{symbol} ")),
+                        Span::styled(name, style),
+                        Span::styled(format!("  {}", item.kind.name()), Style::default().fg(self.theme.muted)),
+                    ])];
+                    if !collected {
+                        lines.push(Line::styled(
+                            format!("    {} Replay {}", self.glyphs.then, self.replay_label(&item.unlock)),
+                            Style::default().fg(self.theme.hint),
+                        ));
+                    }
+                    ListItem::new(lines)
+                })
+                .collect();
+
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(self.theme.selection)
+                .highlight_symbol(self.glyphs.pointer);
+            f.render_stateful_widget(list, chunks[1], &mut self.collection_list_state);
+        }
+
+        let help_text = Paragraph::new(Line::from(vec![
+            Span::styled("Controls: ", Style::default().fg(self.theme.label).add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{}: Move | m: Missing only | Tab: Switch | h: Help | q: Quit", self.glyphs.arrows)),
+        ]))
+        .style(Style::default().fg(self.theme.hint))
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(help_text, chunks[2]);
+    }
+
+    fn replay_label(&self, unlock: &Unlock) -> String {
+        if self.is_unlock_hidden(unlock) {
+            return HIDDEN_TEXT.to_string();
+        }
+        let Some((route_idx, chapter_idx)) = self.game_data.unlock_location(unlock) else {
+            return match unlock {
+                Unlock::Step(step_id) => step_id.clone(),
+                Unlock::Route(name) => name.clone(),
+            };
+        };
+
+        let route = &self.game_data.routes[route_idx];
+        match chapter_idx {
+            Some(chapter_idx) if !self.game_data.is_chapter_hidden(route_idx, chapter_idx, self.spoilers) => {
+                format!("{}, {}", route.name, route.chapters[chapter_idx].name)
+            }
+            _ => route.name.clone(),
+        }
+    }
+
+    fn is_unlock_hidden(&self, unlock: &Unlock) -> bool {
+        self.game_data.unlock_location(unlock)
+            .is_some_and(|(route_idx, _)| self.game_data.is_route_hidden(route_idx, self.spoilers))
+    }
+
+    // Names the step itself, so it also has to stay hidden while the step's
+    // chapter is.
+    fn unlock_label(&self, unlock: &Unlock) -> String {
+        let hidden = match self.game_data.unlock_location(unlock) {
+            Some((route_idx, Some(chapter_idx))) => self.game_data.is_chapter_hidden(route_idx, chapter_idx, self.spoilers),
+            _ => self.is_unlock_hidden(unlock),
+        };
+        if hidden {
+            return HIDDEN_TEXT.to_string();
+        }
+
+        match unlock {
+            Unlock::Step(step_id) => self.game_data.step(step_id)
+                .map_or_else(|| step_id.clone(), |step| step.description.clone()),
+            Unlock::Route(name) => format!("Finish {name}"),
+        }
+    }
+
+    fn draw_route_details(&mut self, f: &mut Frame, area: Rect, route_idx: usize, controls: bool) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(5),
                 Constraint::Min(0),
-                Constraint::Length(4),
+                Constraint::Length(if controls { 4 } else { 0 }),
             ])
             .split(area);
 
         if let Some(route) = self.game_data.routes.get(route_idx) {
             let completion = self.game_data.get_completion_percentage(route_idx);
-            
+            let hidden = self.game_data.is_route_hidden(route_idx, self.spoilers);
+            let (name, description) = if hidden {
+                (HIDDEN_TEXT, "Complete earlier routes to reveal this one")
+            } else {
+                (route.name.as_str(), route.description.as_str())
+            };
+
+            let mut progress = vec![Span::raw(format!("Progress: {:.0}%", completion))];
+            if !hidden && !self.game_data.is_ending_reachable(route_idx) {
+                let needs: Vec<String> = self.game_data.ending_blockers(route_idx).iter().map(ToString::to_string).collect();
+                progress.push(Span::styled(
+                    format!("  {} Locked out of this ending, needs {}", self.glyphs.warning, needs.join(", ")),
+                    Style::default().fg(self.theme.bad).add_modifier(Modifier::BOLD),
+                ));
+            }
+
             let header = Paragraph::new(vec![
                 Line::from(vec![
-                    Span::styled(&route.name, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                ]),
-                Line::from(vec![
-                    Span::raw(&route.description),
+                    Span::styled(name, Style::default().fg(self.theme.title).add_modifier(Modifier::BOLD)),
                 ]),
                 Line::from(vec![
-                    Span::raw(format!("Progress: {:.0}%", completion)),
+                    Span::raw(description),
                 ]),
+                Line::from(progress),
             ])
             .block(Block::default().borders(Borders::ALL));
             f.render_widget(header, chunks[0]);
 
             let mut display_items: Vec<ListItem> = Vec::new();
-            let mut last_seen_chapter: Option<usize> = None;
-            
-            for (display_idx, step_mapping) in self.display_to_step_mapping.iter().enumerate() {
-                match step_mapping {
-                    None => {
-                        let mut chapter_for_this_none = None;
-                        for future_idx in (display_idx + 1)..self.display_to_step_mapping.len() {
-                            if let Some(Some(next_step_idx)) = self.display_to_step_mapping.get(future_idx) {
-                                if let Some((chapter_idx, _)) = self.step_indices.get(*next_step_idx) {
-                                    chapter_for_this_none = Some(*chapter_idx);
-                                    break;
-                                }
-                            }
-                        }
-                        
-                        if let Some(chapter_idx) = chapter_for_this_none {
-                            if last_seen_chapter != Some(chapter_idx) {
-                                last_seen_chapter = Some(chapter_idx);
-                                if let Some(chapter) = route.chapters.get(chapter_idx) {
-                                    display_items.push(ListItem::new(Line::from(vec![
-                                        Span::styled(
-                                            format!("═══ {} ═══", chapter.name),
-                                            Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)
-                                        ),
-                                    ])));
-                                }
+
+            for row in &self.rows {
+                match *row {
+                    Row::Chapter { chapter_idx } => {
+                        if let Some(chapter) = route.chapters.get(chapter_idx) {
+                            let fold = if self.collapsed.contains(&chapter_idx) { self.glyphs.collapsed } else { self.glyphs.expanded };
+                            let rule = self.glyphs.rule;
+                            let mut spans = Vec::new();
+                            if self.game_data.is_chapter_hidden(route_idx, chapter_idx, self.spoilers) {
+                                spans.push(Span::styled(
+                                    format!("{fold} {rule} {HIDDEN_TEXT} {rule}"),
+                                    Style::default().fg(self.theme.chapter).add_modifier(Modifier::BOLD),
+                                ));
                             } else {
-                                display_items.push(ListItem::new(Line::from("")));
+                                let (done, total) = self.game_data.chapter_progress(route_idx, chapter_idx);
+                                spans.push(Span::styled(
+                                    format!("{fold} {rule} {} {rule} ", chapter.name),
+                                    Style::default().fg(self.theme.chapter).add_modifier(Modifier::BOLD),
+                                ));
+                                spans.push(Span::styled(
+                                    format!("{done}/{total}"),
+                                    if total > 0 && done == total {
+                                        Style::default().fg(self.theme.good)
+                                    } else {
+                                        Style::default().fg(self.theme.hint)
+                                    },
+                                ));
                             }
-                        } else {
-                            display_items.push(ListItem::new(Line::from("")));
+                            display_items.push(ListItem::new(Line::from(spans)));
                         }
                     }
-                    Some(step_idx) => {
-                        if let Some((chapter_idx, step_idx_in_chapter)) = self.step_indices.get(*step_idx) {
-                            if last_seen_chapter != Some(*chapter_idx) {
-                                last_seen_chapter = Some(*chapter_idx);
+                    Row::Step { chapter_idx, step_idx } => {
+                        if let Some(step) = route.chapters.get(chapter_idx)
+                            .and_then(|chapter| chapter.steps.get(step_idx))
+                        {
+                            let mismatched = self.game_data.is_step_mismatched(step);
+                            let skipped = self.game_data.is_skipped(&step.id);
+                            let status_symbol = if mismatched {
+                                self.glyphs.wrong
+                            } else if step.completed {
+                                self.glyphs.done
+                            } else if skipped {
+                                self.glyphs.skipped
+                            } else {
+                                self.glyphs.pending
+                            };
+                            let style = if mismatched {
+                                Style::default().fg(self.theme.bad)
+                            } else if skipped {
+                                Style::default().fg(self.theme.muted)
+                            } else if step.completed {
+                                Style::default().fg(self.theme.good)
+                            } else {
+                                Style::default().fg(self.theme.text)
+                            };
+                            let description = if self.game_data.is_chapter_hidden(route_idx, chapter_idx, self.spoilers) {
+                                HIDDEN_TEXT
+                            } else {
+                                step.description.as_str()
+                            };
+
+                            let mut spans = vec![
+                                Span::raw(format!("  {} ", status_symbol)),
+                                Span::styled(description, style),
+                            ];
+                            if let Some(slot) = self.game_data.save_slot(&step.id) {
+                                spans.push(Span::styled(format!(" [{}]", slot), Style::default().fg(self.theme.muted)));
                             }
-                            
-                            if let Some(chapter) = route.chapters.get(*chapter_idx) {
-                                if let Some(step) = chapter.steps.get(*step_idx_in_chapter) {
-                                    let status_symbol = if step.completed { "✓" } else { "○" };
-                                    let style = if step.completed {
-                                        Style::default().fg(Color::Green)
-                                    } else {
-                                        Style::default().fg(Color::White)
-                                    };
-
-                                    display_items.push(ListItem::new(Line::from(vec![
-                                        Span::raw(format!("  {} ", status_symbol)),
-                                        Span::styled(&step.description, style),
-                                    ])));
-                                }
+                            if let Some(slot) = self.game_data.reload_slot(&step.id) {
+                                spans.push(Span::styled(format!(" {} {}", self.glyphs.reload, slot), Style::default().fg(self.theme.muted)));
+                            }
+                            if self.game_data.note(&step.id).is_some() {
+                                spans.push(Span::styled(format!(" {}", self.glyphs.note), Style::default().fg(self.theme.accent)));
                             }
+                            display_items.push(ListItem::new(Line::from(spans)));
                         }
                     }
                 }
@@ -424,12 +1177,16 @@ impl App {
 
             let steps_list = List::new(display_items)
                 .block(Block::default().title("Steps").borders(Borders::ALL))
-                .highlight_style(Style::default().bg(Color::DarkGray))
-                .highlight_symbol("► ");
+                .highlight_style(self.theme.selection)
+                .highlight_symbol(self.glyphs.pointer);
 
             f.render_stateful_widget(steps_list, chunks[1], &mut self.step_list_state);
         }
 
+        if !controls {
+            return;
+        }
+
         let keybind_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
@@ -440,71 +1197,138 @@ impl App {
 
         let nav_help = Paragraph::new(vec![
             Line::from(vec![
-                Span::styled("Navigation: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled("Navigation: ", Style::default().fg(self.theme.label).add_modifier(Modifier::BOLD)),
             ]),
-            Line::from("↑/↓: Move | Enter: Details | Esc: Back"),
+            Line::from(format!("{}: Move | Enter: Open | Esc: Back", self.glyphs.arrows)),
         ])
         .block(Block::default().borders(Borders::ALL));
         f.render_widget(nav_help, keybind_chunks[0]);
 
         let action_help = Paragraph::new(vec![
             Line::from(vec![
-                Span::styled("Actions: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled("Actions: ", Style::default().fg(self.theme.label).add_modifier(Modifier::BOLD)),
             ]),
-            Line::from("Space: Toggle | h: Help | q: Quit"),
+            Line::from("Space: Toggle | x: Skip | h: Help"),
         ])
         .block(Block::default().borders(Borders::ALL));
         f.render_widget(action_help, keybind_chunks[1]);
     }
 
-    fn draw_step_details(&mut self, f: &mut Frame, area: Rect, route_idx: usize, chapter_idx: usize, step_idx: usize) {
-        if let Some(route) = self.game_data.routes.get(route_idx) {
-            if let Some(chapter) = route.chapters.get(chapter_idx) {
-                if let Some(step) = chapter.steps.get(step_idx) {
-                    let chunks = Layout::default()
-                        .direction(Direction::Vertical)
-                        .constraints([
-                            Constraint::Length(3),
-                            Constraint::Min(0),
-                            Constraint::Length(2),
-                        ])
-                        .split(area);
-
-                    let status = if step.completed { "✓ COMPLETED" } else { "○ PENDING" };
-                    let status_style = if step.completed {
-                        Style::default().fg(Color::Green)
-                    } else {
-                        Style::default().fg(Color::Yellow)
-                    };
+    fn draw_step_details(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        route_idx: usize,
+        chapter_idx: usize,
+        step_idx: usize,
+        controls: bool,
+    ) {
+        let Some(step) = self.game_data.routes.get(route_idx)
+            .and_then(|route| route.chapters.get(chapter_idx))
+            .and_then(|chapter| chapter.steps.get(step_idx))
+        else {
+            return;
+        };
+        let hidden = self.game_data.is_chapter_hidden(route_idx, chapter_idx, self.spoilers);
 
-                    let title = Paragraph::new(vec![
-                        Line::from(vec![
-                            Span::styled(&step.description, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                            Span::raw("  "),
-                            Span::styled(status, status_style),
-                        ]),
-                    ])
-                    .block(Block::default().borders(Borders::ALL));
-                    f.render_widget(title, chunks[0]);
-
-                    let details = self.format_step_details(step);
-                    let details_paragraph = Paragraph::new(details)
-                        .block(Block::default().title("Details").borders(Borders::ALL))
-                        .wrap(Wrap { trim: true });
-                    f.render_widget(details_paragraph, chunks[1]);
-
-                    let help_text = Paragraph::new(vec![
-                        Line::from(vec![
-                            Span::styled("Controls: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-                            Span::raw("Esc: Back | h: Help | q: Quit"),
-                        ]),
-                    ])
-                    .style(Style::default().fg(Color::Gray))
-                    .block(Block::default().borders(Borders::ALL));
-                    f.render_widget(help_text, chunks[2]);
-                }
-            }
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(5),
+                Constraint::Length(if controls { 3 } else { 0 }),
+            ])
+            .split(area);
+
+        let mismatched = self.game_data.is_step_mismatched(step);
+        let skipped = self.game_data.is_skipped(&step.id);
+        let status = if mismatched {
+            format!("{} WRONG CHOICE", self.glyphs.wrong)
+        } else if step.completed {
+            format!("{} COMPLETED", self.glyphs.done)
+        } else if skipped {
+            format!("{} SKIPPED", self.glyphs.skipped)
+        } else {
+            format!("{} PENDING", self.glyphs.pending)
+        };
+        let status_style = if mismatched {
+            Style::default().fg(self.theme.bad)
+        } else if skipped {
+            Style::default().fg(self.theme.muted)
+        } else if step.completed {
+            Style::default().fg(self.theme.good)
+        } else {
+            Style::default().fg(self.theme.partial)
+        };
+        let description = if hidden { HIDDEN_TEXT } else { step.description.as_str() };
+
+        let title = Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled(description, Style::default().fg(self.theme.title).add_modifier(Modifier::BOLD)),
+                Span::raw("  "),
+                Span::styled(status, status_style),
+                Span::styled(if step.optional { "  optional" } else { "" }, Style::default().fg(self.theme.muted)),
+            ]),
+        ])
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(title, chunks[0]);
+
+        let details = if hidden {
+            Text::from(vec![
+                Line::from(vec![
+                    Span::styled("Hidden to avoid spoilers.", Style::default().fg(self.theme.accent)),
+                ]),
+                Line::from(""),
+                Line::from("Complete the earlier chapters of this route to reveal this step."),
+            ])
+        } else {
+            let mut details = self.format_step_details(step);
+            details.lines.extend(self.format_choice_details(step));
+            details.lines.extend(self.format_save_slot_details(step));
+            details
+        };
+        let details_paragraph = Paragraph::new(details)
+            .block(Block::default().title("Details").borders(Borders::ALL))
+            .wrap(Wrap { trim: true });
+        f.render_widget(details_paragraph, chunks[1]);
+
+        let (notes, notes_title, controls_text) = match &self.input {
+            Some(TextInput { target: InputTarget::Note { .. }, buffer }) => (
+                Text::from(Line::from(vec![
+                    Span::raw(buffer.as_str()),
+                    Span::styled(self.glyphs.cursor, Style::default().fg(self.theme.label)),
+                ])),
+                "Notes (editing)",
+                "Enter: Save note | Esc: Cancel",
+            ),
+            _ => (
+                match self.game_data.note(&step.id) {
+                    Some(note) => Text::from(note),
+                    None => Text::styled("No notes yet. Press n to add one.", Style::default().fg(self.theme.muted)),
+                },
+                "Notes",
+                step_controls(step),
+            ),
+        };
+        let notes_paragraph = Paragraph::new(notes)
+            .block(Block::default().title(notes_title).borders(Borders::ALL))
+            .wrap(Wrap { trim: false });
+        f.render_widget(notes_paragraph, chunks[2]);
+
+        if !controls {
+            return;
         }
+
+        let help_text = Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled("Controls: ", Style::default().fg(self.theme.label).add_modifier(Modifier::BOLD)),
+                Span::raw(controls_text),
+            ]),
+        ])
+        .style(Style::default().fg(self.theme.hint))
+        .block(Block::default().borders(Borders::ALL));
+        f.render_widget(help_text, chunks[3]);
     }
 
     fn format_step_details<'a>(&self, step: &'a Step) -> Text<'a> {
@@ -512,66 +1336,44 @@ impl App {
             StepType::DelusionTrigger { trigger_number, polarity, location } => {
                 Text::from(vec![
                     Line::from(vec![
-                        Span::styled("Type: ", Style::default().fg(Color::Yellow)),
+                        Span::styled("Type: ", Style::default().fg(self.theme.label)),
                         Span::raw("Delusion Trigger"),
                     ]),
                     Line::from(vec![
-                        Span::styled("Trigger Number: ", Style::default().fg(Color::Yellow)),
+                        Span::styled("Trigger Number: ", Style::default().fg(self.theme.label)),
                         Span::raw(format!("#{}", trigger_number)),
                     ]),
                     Line::from(vec![
-                        Span::styled("Polarity: ", Style::default().fg(Color::Yellow)),
-                        Span::styled(format!("{:?}", polarity), match polarity {
-                            Polarity::Positive => Style::default().fg(Color::Green),
-                            Polarity::Negative => Style::default().fg(Color::Red),
-                            Polarity::Neutral => Style::default().fg(Color::Blue),
-                        }),
+                        Span::styled("Polarity: ", Style::default().fg(self.theme.label)),
+                        Span::styled(format!("{:?}", polarity), Style::default().fg(self.theme.polarity(polarity))),
                     ]),
                     Line::from(vec![
-                        Span::styled("Location: ", Style::default().fg(Color::Yellow)),
+                        Span::styled("Location: ", Style::default().fg(self.theme.label)),
                         Span::raw(location),
                     ]),
                 ])
             }
-            StepType::YesNoPrompts { prompts } => {
+            StepType::Choices { prompts } => {
+                let kind = if prompts.iter().all(ChoicePrompt::is_yes_no) { "YES/NO Prompts" } else { "Choices" };
                 let mut lines = vec![
                     Line::from(vec![
-                        Span::styled("Type: ", Style::default().fg(Color::Yellow)),
-                        Span::raw("YES/NO Prompts"),
+                        Span::styled("Type: ", Style::default().fg(self.theme.label)),
+                        Span::raw(kind),
                     ]),
                     Line::from(""),
                 ];
-                
-                for (i, prompt) in prompts.iter().enumerate() {
-                    let answer_text = if prompt.answer { "YES" } else { "NO" };
-                    let answer_style = if prompt.answer {
-                        Style::default().fg(Color::Green)
-                    } else {
-                        Style::default().fg(Color::Red)
-                    };
-                    
-                    lines.push(Line::from(vec![
-                        Span::styled(format!("{}. ", i + 1), Style::default().fg(Color::Cyan)),
-                        Span::raw(&prompt.question),
-                    ]));
-                    lines.push(Line::from(vec![
-                        Span::raw("   Answer: "),
-                        Span::styled(answer_text, answer_style),
-                    ]));
-                    lines.push(Line::from(""));
-                }
-                
+                lines.extend(self.format_prompts(prompts));
                 Text::from(lines)
             }
             StepType::GeneralInstruction { instruction } => {
                 Text::from(vec![
                     Line::from(vec![
-                        Span::styled("Type: ", Style::default().fg(Color::Yellow)),
+                        Span::styled("Type: ", Style::default().fg(self.theme.label)),
                         Span::raw("General Instruction"),
                     ]),
                     Line::from(""),
                     Line::from(vec![
-                        Span::styled("Instructions: ", Style::default().fg(Color::Yellow)),
+                        Span::styled("Instructions: ", Style::default().fg(self.theme.label)),
                     ]),
                     Line::from(instruction.clone()),
                 ])
@@ -579,45 +1381,196 @@ impl App {
             StepType::Checkpoint { save_point } => {
                 Text::from(vec![
                     Line::from(vec![
-                        Span::styled("Type: ", Style::default().fg(Color::Yellow)),
+                        Span::styled("Type: ", Style::default().fg(self.theme.label)),
                         Span::raw("Save Checkpoint"),
                     ]),
                     Line::from(""),
                     Line::from(vec![
-                        Span::styled("Save Point: ", Style::default().fg(Color::Yellow)),
+                        Span::styled("Save Point: ", Style::default().fg(self.theme.label)),
                         Span::raw(save_point),
                     ]),
                     Line::from(""),
                     Line::from(vec![
-                        Span::styled("Remember to save your game at this point!", Style::default().fg(Color::Magenta)),
+                        Span::styled("Remember to save your game at this point!", Style::default().fg(self.theme.accent)),
                     ]),
                 ])
             }
         }
     }
 
+    fn format_prompts<'a>(&self, prompts: &'a [ChoicePrompt]) -> Vec<Line<'a>> {
+        let mut lines = Vec::new();
+
+        for (i, prompt) in prompts.iter().enumerate() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}. ", i + 1), Style::default().fg(self.theme.title)),
+                Span::raw(prompt.question_for(self.spoilers)),
+            ]));
+            if !prompt.is_yes_no() {
+                lines.push(Line::from(format!("   Options: {}", prompt.options.join(" / "))));
+            }
+
+            let mut answer = vec![Span::raw("   Answer: ")];
+            answer.extend(format_pick(&prompt.pick, &self.theme, &self.glyphs));
+            lines.push(Line::from(answer));
+            lines.push(Line::from(""));
+        }
+
+        lines
+    }
+
+    fn format_choice_details(&self, step: &Step) -> Vec<Line<'static>> {
+        let Some(choice) = self.game_data.choice(&step.id) else {
+            return Vec::new();
+        };
+
+        let (style, verdict) = if step.accepts(choice) {
+            (Style::default().fg(self.theme.good), "")
+        } else {
+            (Style::default().fg(self.theme.bad), "  (not what the guide says)")
+        };
+        vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("You Picked: ", Style::default().fg(self.theme.label)),
+                Span::styled(format_choice(choice), style),
+                Span::styled(verdict, style),
+            ]),
+        ]
+    }
+
+    fn format_save_slot_details(&self, step: &Step) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+
+        if matches!(step.step_type, StepType::Checkpoint { .. }) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("Saved In: ", Style::default().fg(self.theme.label)),
+                match self.game_data.save_slot(&step.id) {
+                    Some(slot) => Span::raw(slot.to_string()),
+                    None => Span::styled("not recorded (press s to record it)", Style::default().fg(self.theme.muted)),
+                },
+            ]));
+        }
+
+        if let Some(checkpoint_id) = &step.reload_from {
+            let checkpoint = self.game_data.step(checkpoint_id)
+                .map_or(checkpoint_id.as_str(), |checkpoint| checkpoint.description.as_str());
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("Reload: ", Style::default().fg(self.theme.label)),
+                match self.game_data.save_slot(checkpoint_id) {
+                    Some(slot) => Span::styled(slot.to_string(), Style::default().fg(self.theme.accent)),
+                    None => Span::raw("the save you made"),
+                },
+                Span::raw(format!(" from \"{}\"", checkpoint)),
+            ]));
+        }
+
+        lines
+    }
+
+    fn draw_save_slot_popup(&self, f: &mut Frame, area: Rect, step_id: &str, buffer: &str) {
+        let popup_area = centered_rect(60, 30, area);
+        f.render_widget(Clear, popup_area);
+
+        let save_point = match self.game_data.step(step_id).map(|step| &step.step_type) {
+            Some(StepType::Checkpoint { save_point }) => save_point.as_str(),
+            _ => step_id,
+        };
+
+        let text = Text::from(vec![
+            Line::from(vec![
+                Span::styled("Which save slot did you use?", Style::default().fg(self.theme.title).add_modifier(Modifier::BOLD)),
+            ]),
+            Line::from(Span::styled(save_point, Style::default().fg(self.theme.hint))),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw(buffer),
+                Span::styled(self.glyphs.cursor, Style::default().fg(self.theme.label)),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("Enter: Save | Esc: Skip", Style::default().fg(self.theme.hint))),
+        ]);
+
+        let popup = Paragraph::new(text)
+            .block(Block::default().title("Save Slot").borders(Borders::ALL))
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(popup, popup_area);
+    }
+
+    fn draw_choice_popup(&self, f: &mut Frame, area: Rect, step_id: &str, buffer: &str) {
+        let popup_area = centered_rect(60, 40, area);
+        f.render_widget(Clear, popup_area);
+
+        let options = self.game_data.step(step_id).map(Step::choice_options).unwrap_or_default();
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("What did you pick?", Style::default().fg(self.theme.title).add_modifier(Modifier::BOLD)),
+            ]),
+        ];
+        for (i, prompt_options) in options.iter().enumerate() {
+            lines.push(Line::from(Span::styled(
+                format!("{}. {}", i + 1, prompt_options.join(" / ")),
+                Style::default().fg(self.theme.hint),
+            )));
+        }
+        lines.extend([
+            Line::from(""),
+            Line::from(vec![
+                Span::raw(buffer),
+                Span::styled(self.glyphs.cursor, Style::default().fg(self.theme.label)),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("; between prompts, , between picks", Style::default().fg(self.theme.hint))),
+            Line::from(Span::styled("Enter: Save | Esc: Cancel", Style::default().fg(self.theme.hint))),
+        ]);
+
+        let popup = Paragraph::new(Text::from(lines))
+            .block(Block::default().title("Choice").borders(Borders::ALL))
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(popup, popup_area);
+    }
+
     fn draw_help_popup(&self, f: &mut Frame, area: Rect) {
         let popup_area = centered_rect(60, 50, area);
         f.render_widget(Clear, popup_area);
 
+        let g = &self.glyphs;
         let help_text = Text::from(vec![
-            Line::from(vec![Span::styled("Help", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]),
+            Line::from(vec![Span::styled("Help", Style::default().fg(self.theme.title).add_modifier(Modifier::BOLD))]),
             Line::from(""),
             Line::from("Navigation:"),
-            Line::from("  ↑/↓ - Move up/down"),
-            Line::from("  Enter - Select/Enter"),
+            Line::from(format!("  {} - Move up/down", g.arrows)),
+            Line::from("  Enter - Select/Enter, or fold a chapter"),
             Line::from("  Esc - Go back"),
+            Line::from("  Tab - Switch to the next list"),
+            Line::from(format!("  {} - Filter TIPS by chapter", g.sideways)),
+            Line::from("  m - Show only missing collection items"),
             Line::from(""),
             Line::from("Actions:"),
             Line::from("  Space - Toggle step completion"),
+            Line::from("  x - Mark a step as skipped"),
+            Line::from("  n - Edit the note on a step"),
+            Line::from("  s - Record the save slot of a checkpoint"),
+            Line::from("  c - Record what you picked at a choice"),
+            Line::from("  z - Collapse/expand the current chapter"),
             Line::from("  h - Toggle this help"),
             Line::from("  q - Quit application"),
             Line::from(""),
             Line::from("Symbols:"),
-            Line::from("  ✓ - Completed"),
-            Line::from("  ○ - Not completed"),
-            Line::from("  ◐ - Partially completed"),
-            Line::from("  🔒 - Prerequisites not met"),
+            Line::from(format!("  {} - Completed", g.done)),
+            Line::from(format!("  {} - Not completed", g.pending)),
+            Line::from(format!("  {} - Skipped", g.skipped)),
+            Line::from(format!("  {}/{} - Collapsed/expanded chapter", g.collapsed, g.expanded)),
+            Line::from(format!("  {} - Picked differently from the guide", g.wrong)),
+            Line::from(format!("  {} - Partially completed", g.partial)),
+            Line::from(format!("  {} - Prerequisites not met", g.locked)),
+            Line::from("  ??? - Hidden to avoid spoilers"),
+            Line::from(format!("  {} - Has a note", g.note)),
+            Line::from(format!("  {} - Reload this save slot first", g.reload)),
             Line::from(""),
             Line::from("Press h or Esc to close this help."),
         ]);
@@ -628,27 +1581,77 @@ impl App {
 
         f.render_widget(help_popup, popup_area);
     }
+}
 
-    fn is_route_available(&self, route_idx: usize) -> bool {
-        if let Some(route) = self.game_data.routes.get(route_idx) {
-            for prereq in &route.prerequisites {
-                let prereq_completed = self.game_data.routes.iter()
-                    .find(|r| r.name == *prereq)
-                    .map(|r| {
-                        let route_idx = self.game_data.routes.iter().position(|x| x.name == r.name).unwrap();
-                        self.game_data.get_completion_percentage(route_idx) >= 100.0
-                    })
-                    .unwrap_or(false);
-                
-                if !prereq_completed {
-                    return false;
-                }
-            }
+fn step_controls(step: &Step) -> &'static str {
+    if matches!(step.step_type, StepType::Checkpoint { .. }) {
+        "n: Edit note | s: Save slot | Esc: Back | h: Help | q: Quit"
+    } else if !step.choice_options().is_empty() {
+        "n: Edit note | c: Record choice | Esc: Back | h: Help | q: Quit"
+    } else {
+        "n: Edit note | Esc: Back | h: Help | q: Quit"
+    }
+}
+
+fn format_pick<'a>(pick: &'a Pick, theme: &Theme, glyphs: &Glyphs) -> Vec<Span<'a>> {
+    let answer_style = |option: &str| match option {
+        ChoicePrompt::YES => Style::default().fg(theme.good),
+        ChoicePrompt::NO => Style::default().fg(theme.bad),
+        _ => Style::default().fg(theme.good),
+    };
+
+    match pick {
+        Pick::Any => vec![Span::styled("any", Style::default().fg(theme.hint))],
+        Pick::OneOf(options) if options.len() == 1 => {
+            vec![Span::styled(options[0].as_str(), answer_style(&options[0]))]
         }
-        true
+        Pick::OneOf(options) => vec![
+            Span::raw("any of "),
+            Span::styled(options.join(", "), Style::default().fg(theme.good)),
+        ],
+        Pick::AllOf { options, ordered: true } => vec![
+            Span::styled(options.join(&format!(" {} ", glyphs.then)), Style::default().fg(theme.good)),
+            Span::raw(" (in this order)"),
+        ],
+        Pick::AllOf { options, ordered: false } => vec![
+            Span::styled(options.join(" + "), Style::default().fg(theme.good)),
+            Span::raw(" (any order)"),
+        ],
     }
 }
 
+fn format_choice(choice: &[Vec<String>]) -> String {
+    choice.iter().map(|picked| picked.join(", ")).collect::<Vec<_>>().join("; ")
+}
+
+// Reads "Positive" or "YES; NO, YES" style input, matching each pick against
+// the step's options case-insensitively or by unique prefix.
+fn parse_choice(step: &Step, input: &str) -> Option<Vec<Vec<String>>> {
+    if input.trim().is_empty() {
+        return Some(Vec::new());
+    }
+
+    let options = step.choice_options();
+    let prompts: Vec<&str> = input.split(';').collect();
+    if prompts.len() != options.len() {
+        return None;
+    }
+
+    prompts.iter().zip(&options).map(|(picks, prompt_options)| {
+        picks.split(',').map(str::trim).filter(|pick| !pick.is_empty()).map(|pick| {
+            let pick = pick.to_lowercase();
+            if let Some(exact) = prompt_options.iter().find(|option| option.to_lowercase() == pick) {
+                return Some(exact.clone());
+            }
+            let mut prefixed = prompt_options.iter().filter(|option| option.to_lowercase().starts_with(&pick));
+            match (prefixed.next(), prefixed.next()) {
+                (Some(option), None) => Some(option.clone()),
+                _ => None,
+            }
+        }).collect()
+    }).collect()
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -669,18 +1672,21 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-pub fn run_app() -> Result<()> {
+pub fn run_app(game_data: GameData, store: ProgressStore, preferences: Preferences) -> Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let routes = crate::data::create_chaos_head_data();
-    let mut game_data = GameData::new();
-    game_data.routes = routes;
-
-    let app = App::new(game_data);
+    let mut app = App::new(game_data, preferences.spoilers);
+    app.theme = Theme::new(preferences.theme);
+    app.glyphs = Glyphs::new(preferences.glyphs);
+    app.keymap = preferences.keymap;
+    app.collapse_completed = preferences.collapse_completed;
+    app.preview_route();
+    app.store = Some(store);
+    app.sync_from_disk()?;
     let res = app.run(&mut terminal);
 
     disable_raw_mode()?;
@@ -697,3 +1703,6 @@ pub fn run_app() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests;
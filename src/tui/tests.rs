@@ -0,0 +1,755 @@
+use super::*;
+use insta::assert_snapshot;
+use ratatui::backend::TestBackend;
+use ratatui::style::Color;
+use crate::glyphs::GlyphMode;
+use crate::theme::ThemeName;
+
+struct Harness {
+    app: App,
+    terminal: Terminal<TestBackend>,
+}
+
+impl Harness {
+    fn new(spoilers: SpoilerLevel) -> Self {
+        Self::with_game(crate::load_title("chaos_head").unwrap(), spoilers)
+    }
+
+    fn with_game(game_data: GameData, spoilers: SpoilerLevel) -> Self {
+        Self {
+            app: App::new(game_data, spoilers),
+            terminal: Terminal::new(TestBackend::new(80, 24)).unwrap(),
+        }
+    }
+
+    fn resize(&mut self, width: u16, height: u16) -> &mut Self {
+        self.terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        self
+    }
+
+    fn press(&mut self, keys: &[KeyCode]) -> &mut Self {
+        for key in keys {
+            self.app.handle_key_event(*key);
+        }
+        self
+    }
+
+    fn screen(&mut self) -> String {
+        self.terminal.draw(|f| self.app.draw(f)).unwrap();
+        let buffer = self.terminal.backend().buffer();
+
+        (0..buffer.area.height)
+            .map(|y| {
+                let line: String = (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect();
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn selected_step(&self) -> Option<(usize, usize)> {
+        match self.app.selected_row()? {
+            Row::Step { chapter_idx, step_idx } => Some((chapter_idx, step_idx)),
+            Row::Chapter { .. } => None,
+        }
+    }
+}
+
+#[test]
+fn rows_interleave_chapter_headers() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter]);
+
+    let chapter = |chapter_idx| Row::Chapter { chapter_idx };
+    let step = |chapter_idx, step_idx| Row::Step { chapter_idx, step_idx };
+    assert_eq!(h.app.rows, vec![
+        chapter(0), step(0, 0), chapter(1), step(1, 0), chapter(2), step(2, 0), step(2, 1), step(2, 2), chapter(3), step(3, 0),
+    ]);
+    assert_eq!(h.app.step_list_state.selected(), Some(1));
+}
+
+#[test]
+fn move_down_visits_chapter_headers_and_wraps() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Down]);
+    assert_eq!(h.app.step_list_state.selected(), Some(2));
+    assert_eq!(h.app.selected_row(), Some(Row::Chapter { chapter_idx: 1 }));
+
+    h.press(&[KeyCode::Down; 7]);
+    assert_eq!(h.app.step_list_state.selected(), Some(9));
+
+    h.press(&[KeyCode::Down]);
+    assert_eq!(h.app.step_list_state.selected(), Some(0));
+}
+
+#[test]
+fn move_up_visits_chapter_headers_and_wraps() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Up]);
+    assert_eq!(h.app.step_list_state.selected(), Some(0));
+
+    h.press(&[KeyCode::Up]);
+    assert_eq!(h.app.step_list_state.selected(), Some(9));
+
+    h.press(&[KeyCode::Up, KeyCode::Up]);
+    assert_eq!(h.app.step_list_state.selected(), Some(7));
+}
+
+#[test]
+fn chapter_headers_show_progress_and_fold() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Char(' ')]);
+    let screen = h.screen();
+    assert!(screen.contains("▾ ═══ Chapter One ═══ 1/1"), "{screen}");
+    assert!(screen.contains("▾ ═══ Chapter Six ═══ 0/2"));
+
+    h.press(&[KeyCode::Char('z')]);
+    assert!(h.app.collapsed.contains(&0));
+    assert_eq!(h.app.selected_row(), Some(Row::Chapter { chapter_idx: 0 }));
+    assert_eq!(h.app.rows.len(), 9);
+    assert!(h.screen().contains("▸ ═══ Chapter One ═══ 1/1"));
+
+    h.press(&[KeyCode::Enter]);
+    assert!(h.app.collapsed.is_empty());
+    assert_eq!(h.app.rows.len(), 10);
+    assert!(matches!(h.app.current_view, View::RouteDetails { .. }));
+}
+
+#[test]
+fn completed_chapters_can_collapse_automatically() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.app.collapse_completed = true;
+    h.press(&[KeyCode::Enter, KeyCode::Char(' ')]);
+
+    assert!(h.app.collapsed.contains(&0));
+    assert_eq!(h.app.selected_row(), Some(Row::Chapter { chapter_idx: 0 }));
+
+    h.press(&[KeyCode::Esc, KeyCode::Enter]);
+    assert_eq!(h.app.collapsed, HashSet::from([0]));
+    assert_eq!(h.app.selected_row(), Some(Row::Step { chapter_idx: 1, step_idx: 0 }));
+}
+
+#[test]
+fn route_list_clamps_at_both_ends() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Up]);
+    assert_eq!(h.app.route_list_state.selected(), Some(0));
+
+    h.press(&[KeyCode::Down; 20]);
+    assert_eq!(h.app.route_list_state.selected(), Some(h.app.game_data.routes.len() - 1));
+}
+
+#[test]
+fn select_current_walks_down_the_views() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Down, KeyCode::Enter]);
+    assert!(matches!(h.app.current_view, View::RouteDetails { route_idx: 1 }));
+    assert_eq!(h.app.game_data.current_route, Some(1));
+
+    h.press(&[KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+    assert!(matches!(
+        h.app.current_view,
+        View::StepDetails { route_idx: 1, chapter_idx: 1, step_idx: 0 }
+    ));
+
+    h.press(&[KeyCode::Esc]);
+    assert!(matches!(h.app.current_view, View::RouteDetails { route_idx: 1 }));
+
+    h.press(&[KeyCode::Esc]);
+    assert!(matches!(h.app.current_view, View::RouteSelection));
+    assert_eq!(h.app.step_list_state.selected(), None);
+}
+
+#[test]
+fn the_highlighted_route_is_previewed_without_drawing() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    let first = h.app.rows.clone();
+    assert!(!first.is_empty());
+
+    h.press(&[KeyCode::Down]);
+    assert_ne!(h.app.rows, first);
+    assert_eq!(h.app.step_list_state.selected(), None);
+
+    let previewed = h.app.rows.clone();
+    h.resize(160, 40).screen();
+    assert_eq!(h.app.rows, previewed);
+}
+
+#[test]
+fn space_toggles_the_selected_step() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Down, KeyCode::Down, KeyCode::Char(' ')]);
+
+    assert_eq!(h.selected_step(), Some((1, 0)));
+    assert!(h.app.game_data.routes[0].chapters[1].steps[0].completed);
+    assert_eq!(h.app.game_data.progress.get("silent_sky_ch2_hospital"), Some(&true));
+    assert!(h.screen().contains("✓ Answer hospital checklist questions"));
+
+    h.press(&[KeyCode::Char(' ')]);
+    assert!(!h.app.game_data.routes[0].chapters[1].steps[0].completed);
+}
+
+#[test]
+fn x_marks_the_selected_step_as_skipped() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Down, KeyCode::Down, KeyCode::Char(' '), KeyCode::Char('x')]);
+
+    assert!(h.app.game_data.is_skipped("silent_sky_ch2_hospital"));
+    assert!(!h.app.game_data.routes[0].chapters[1].steps[0].completed);
+    assert!(h.screen().contains("↷ Answer hospital checklist questions"));
+
+    h.press(&[KeyCode::Enter]);
+    assert!(h.screen().contains("↷ SKIPPED"));
+    h.press(&[KeyCode::Char('x')]);
+    assert!(h.screen().contains("○ PENDING"));
+}
+
+#[test]
+fn vim_keymap_moves_with_j_and_k() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Char('j')]);
+    assert_eq!(h.selected_step(), Some((0, 0)));
+
+    h.app.keymap = Keymap::Vim;
+    h.press(&[KeyCode::Char('j'), KeyCode::Char('j'), KeyCode::Char('j'), KeyCode::Char('k')]);
+    assert_eq!(h.selected_step(), Some((1, 0)));
+}
+
+#[test]
+fn help_popup_swallows_keys_until_closed() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Char('h'), KeyCode::Down, KeyCode::Char('q')]);
+    assert!(h.app.show_help);
+    assert!(!h.app.should_quit);
+    assert_eq!(h.app.route_list_state.selected(), Some(0));
+
+    h.press(&[KeyCode::Esc, KeyCode::Char('q')]);
+    assert!(!h.app.show_help);
+    assert!(h.app.should_quit);
+}
+
+#[test]
+fn completing_a_route_unlocks_the_next() {
+    let mut h = Harness::new(SpoilerLevel::Routes);
+    assert!(!h.screen().contains("Crying Sky"));
+
+    h.press(&[KeyCode::Enter]);
+    for _ in 0..h.app.rows.len() {
+        h.press(&[KeyCode::Char(' ')]);
+        if h.app.input.is_some() {
+            h.press(&[KeyCode::Esc]);
+        }
+        h.press(&[KeyCode::Down]);
+    }
+    h.press(&[KeyCode::Esc]);
+
+    assert!(h.screen().contains("Crying Sky"));
+}
+
+#[test]
+fn notes_are_edited_inline_in_step_details() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Enter, KeyCode::Char('n')]);
+    for c in "hq after rooftop".chars() {
+        h.press(&[KeyCode::Char(c)]);
+    }
+    h.press(&[KeyCode::Backspace, KeyCode::Char('p'), KeyCode::Enter]);
+
+    assert!(!h.app.should_quit);
+    assert!(!h.app.show_help);
+    assert_eq!(h.app.game_data.note("silent_sky_ch1_swimsuit"), Some("hq after rooftop"));
+    assert!(h.app.unsaved_changes);
+
+    h.press(&[KeyCode::Char('n'), KeyCode::Char('!'), KeyCode::Esc]);
+    assert_eq!(h.app.game_data.note("silent_sky_ch1_swimsuit"), Some("hq after rooftop"));
+
+    h.press(&[KeyCode::Esc]);
+    assert!(h.screen().contains("Answer swimsuit questions as you'd like ✎"));
+}
+
+#[test]
+fn completing_a_checkpoint_asks_for_the_save_slot() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Char(' ')]);
+    assert!(matches!(
+        &h.app.input,
+        Some(TextInput { target: InputTarget::SaveSlot { step_id }, .. }) if step_id == "silent_sky_ch6_save"
+    ));
+
+    for c in "Slot 3".chars() {
+        h.press(&[KeyCode::Char(c)]);
+    }
+    h.press(&[KeyCode::Enter]);
+    assert_eq!(h.app.game_data.save_slot("silent_sky_ch6_save"), Some("Slot 3"));
+
+    let screen = h.screen();
+    assert!(screen.contains("Save your game before O-FRONT scene [Slot 3]"));
+    assert!(screen.contains("Optional: View bad ending (NO, NO, NO) ↺ Slot 3"));
+
+    h.press(&[KeyCode::Down, KeyCode::Enter]);
+    assert!(h.resize(80, 40).screen().contains("Reload: Slot 3 from \"Save your game before O-FRONT scene\""));
+}
+
+#[test]
+fn skipping_the_save_slot_prompt_still_completes_the_checkpoint() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Char(' '), KeyCode::Esc]);
+
+    assert!(h.app.input.is_none());
+    assert!(matches!(h.app.current_view, View::RouteDetails { .. }));
+    assert!(h.app.game_data.step("silent_sky_ch6_save").unwrap().completed);
+    assert_eq!(h.app.game_data.save_slot("silent_sky_ch6_save"), None);
+
+    h.press(&[KeyCode::Char(' ')]);
+    assert!(h.app.input.is_none());
+}
+
+#[test]
+fn skipping_ahead_warns_about_a_locked_out_ending() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+    assert!(!h.screen().contains("Locked out"));
+
+    h.press(&[KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Char(' ')]);
+    assert_eq!(h.app.game_data.progress.get("daydream_ending"), Some(&true));
+    let screen = h.screen();
+    assert!(screen.contains("Locked out of this ending, needs dt1_negative"), "{screen}");
+
+    h.press(&[KeyCode::Char(' ')]);
+    assert!(!h.screen().contains("Locked out"));
+}
+
+#[test]
+fn counter_requirements_show_in_the_route_header() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Down, KeyCode::Enter]);
+
+    assert!(matches!(h.app.current_view, View::RouteDetails { route_idx: 1 }));
+    assert!(h.resize(120, 24).screen().contains("Locked out of this ending, needs delusions ≥ 11"));
+}
+
+#[test]
+fn recording_the_wrong_polarity_flags_the_step() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Down, KeyCode::Down, KeyCode::Enter, KeyCode::Enter, KeyCode::Char('c')]);
+    assert!(h.screen().contains("Positive / Negative / Neutral"));
+
+    h.press(&[KeyCode::Char('x'), KeyCode::Enter]);
+    assert!(h.app.input.is_some(), "unknown picks keep the prompt open");
+
+    h.press(&[KeyCode::Backspace, KeyCode::Char('p'), KeyCode::Char('o'), KeyCode::Enter]);
+    assert!(h.app.input.is_none());
+    assert_eq!(h.app.game_data.choice("daydream_dt1"), Some([vec!["Positive".to_string()]].as_slice()));
+    let screen = h.resize(80, 40).screen();
+    assert!(screen.contains("✗ WRONG CHOICE"), "{screen}");
+    assert!(screen.contains("You Picked: Positive  (not what the guide says)"));
+
+    let screen = h.press(&[KeyCode::Esc]).screen();
+    assert!(screen.contains("✗ Delusion Trigger #1"), "{screen}");
+    assert!(screen.contains("Locked out of this ending, needs dt1_negative"));
+}
+
+#[test]
+fn choices_are_parsed_per_prompt() {
+    let game = crate::load_title("chaos_head").unwrap();
+    let trigger = game.step("daydream_dt1").unwrap();
+    let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(parse_choice(trigger, " neg "), Some(vec![strings(&["Negative"])]));
+    assert_eq!(parse_choice(trigger, "ne"), None, "ambiguous prefix");
+    assert_eq!(parse_choice(trigger, "Negative; Positive"), None);
+    assert_eq!(parse_choice(trigger, ""), Some(vec![]));
+
+    let step = Step {
+        id: "reply".to_string(),
+        description: String::new(),
+        step_type: StepType::Choices { prompts: vec![
+            ChoicePrompt::yes_no("Go?", true),
+            ChoicePrompt { question: "Reply".to_string(), short_question: None, options: strings(&["Sticker", "Text"]), pick: Pick::Any },
+        ] },
+        completed: false,
+        optional: false,
+        reload_from: None,
+        effects: vec![],
+        editions: vec![],
+    };
+    let choice = parse_choice(&step, "y; text, sticker").unwrap();
+    assert_eq!(choice, vec![strings(&["YES"]), strings(&["Text", "Sticker"])]);
+    assert_eq!(format_choice(&choice), "YES; Text, Sticker");
+}
+
+#[test]
+fn no_color_theme_draws_without_colours() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.app.theme = Theme::new(ThemeName::NoColor);
+    h.press(&[KeyCode::Enter, KeyCode::Down, KeyCode::Char(' ')]);
+    h.screen();
+
+    let buffer = h.terminal.backend().buffer();
+    assert!(buffer.content().iter().all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+    assert!(buffer.content().iter().any(|cell| cell.modifier.contains(Modifier::REVERSED)));
+}
+
+#[test]
+fn wide_terminals_preview_the_hovered_route() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.resize(160, 30);
+    let screen = h.screen();
+    assert!(screen.contains("Answer swimsuit questions"));
+    assert!(screen.contains("Select a step to see its details here."));
+
+    h.press(&[KeyCode::Down]);
+    assert!(!h.screen().contains("Answer swimsuit questions"));
+    assert!(matches!(h.app.current_view, View::RouteSelection));
+}
+
+#[test]
+fn wide_terminals_show_the_selected_step_beside_the_list() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.resize(160, 30);
+    h.press(&[KeyCode::Enter]);
+    let screen = h.screen();
+    assert!(screen.contains("Series of YES/NO questions about school swimsuits"));
+
+    h.press(&[KeyCode::Down, KeyCode::Down, KeyCode::Enter, KeyCode::Esc]);
+    assert_eq!(h.selected_step(), Some((1, 0)));
+    assert!(h.screen().contains("Answer hospital checklist questions"));
+}
+
+#[test]
+fn route_selection_is_titled_with_the_edition() {
+    assert!(Harness::new(SpoilerLevel::Off).screen().contains("Chaos;Head NoAH - Route Guide"));
+
+    let original = crate::load_edition("chaos_head", Some("original")).unwrap();
+    let screen = Harness::with_game(original, SpoilerLevel::Off).screen();
+    assert!(screen.contains("Chaos;Head - Route Guide"));
+    assert!(!screen.contains("Daydream"));
+}
+
+#[test]
+fn multiple_choice_prompts_list_options_and_picks() {
+    let options = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    let prompts = vec![
+        ChoicePrompt { question: "Reply".to_string(), short_question: None, options: options(&["Sticker", "Text", "Ignore"]), pick: Pick::OneOf(options(&["Sticker", "Text"])) },
+        ChoicePrompt { question: "Order".to_string(), short_question: None, options: options(&["A", "B"]), pick: Pick::AllOf { options: options(&["B", "A"]), ordered: true } },
+        ChoicePrompt { question: "Whatever".to_string(), short_question: None, options: options(&["X", "Y"]), pick: Pick::Any },
+    ];
+    let game_data = GameData::with_routes(vec![Route {
+        name: "Route".to_string(),
+        description: String::new(),
+        prerequisites: vec![],
+        requires: vec![],
+        editions: vec![],
+        chapters: vec![Chapter {
+            number: 1,
+            name: "Chapter One".to_string(),
+            steps: vec![Step {
+                id: "choices".to_string(),
+                description: "Phone replies".to_string(),
+                step_type: StepType::Choices { prompts },
+                completed: false,
+                optional: false,
+                reload_from: None,
+                effects: vec![],
+                editions: vec![],
+            }],
+        }],
+    }]);
+
+    let mut h = Harness::with_game(game_data, SpoilerLevel::Off);
+    let screen = h.press(&[KeyCode::Enter, KeyCode::Enter]).resize(80, 40).screen();
+
+    assert!(screen.contains("Type: Choices"));
+    assert!(screen.contains("Options: Sticker / Text / Ignore"));
+    assert!(screen.contains("Answer: any of Sticker, Text"));
+    assert!(screen.contains("Answer: B → A (in this order)"));
+    assert!(screen.contains("Answer: any"));
+}
+
+fn with_achievements() -> GameData {
+    let achievement = |id: &str, name: &str, hidden, unlock| Achievement {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: format!("Description of {name}"),
+        hidden,
+        unlock,
+        editions: vec![],
+    };
+    crate::load_title("chaos_head").unwrap().with_achievements(vec![
+        achievement("swimsuit", "Swimsuit", false, Some(Unlock::Step("silent_sky_ch1_swimsuit".to_string()))),
+        achievement("crying_sky", "Crying", true, Some(Unlock::Route("Crying Sky".to_string()))),
+        achievement("collector", "Collector", false, None),
+    ])
+}
+
+#[test]
+fn tab_opens_achievements_and_esc_returns_to_routes() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Tab]);
+    assert!(matches!(h.app.current_view, View::Achievements));
+    assert!(h.screen().contains("No achievements are listed for this title yet."));
+
+    h.press(&[KeyCode::Esc]);
+    assert!(matches!(h.app.current_view, View::RouteSelection));
+
+    h.press(&[KeyCode::Enter, KeyCode::Tab]);
+    assert!(matches!(h.app.current_view, View::RouteDetails { .. }));
+}
+
+#[test]
+fn space_marks_only_achievements_not_earned_through_the_guide() {
+    let mut h = Harness::with_game(with_achievements(), SpoilerLevel::Off);
+    h.app.game_data.mark_step_completed("silent_sky_ch1_swimsuit", true);
+    h.press(&[KeyCode::Tab, KeyCode::Char(' ')]);
+    assert!(h.app.game_data.unlocked.is_empty());
+
+    h.press(&[KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Char(' ')]);
+    assert_eq!(h.app.game_data.unlocked, HashSet::from(["collector".to_string()]));
+    assert!(h.app.unsaved_changes);
+    assert!(h.screen().contains("Achievements 2/3 (67%)"));
+}
+
+#[test]
+fn unlock_steps_in_unreached_chapters_stay_hidden() {
+    let game = || crate::load_title("chaos_head").unwrap().with_achievements(vec![Achievement {
+        id: "silent_sky_end".to_string(),
+        name: "Silence".to_string(),
+        description: String::new(),
+        hidden: false,
+        unlock: Some(Unlock::Step("silent_sky_ch10_ending".to_string())),
+        editions: vec![],
+    }]);
+
+    let screen = Harness::with_game(game(), SpoilerLevel::Routes).press(&[KeyCode::Tab]).screen();
+    assert!(screen.contains("Achieve Silent Sky ending"), "{screen}");
+
+    let screen = Harness::with_game(game(), SpoilerLevel::Chapters).press(&[KeyCode::Tab]).screen();
+    assert!(!screen.contains("Achieve Silent Sky ending"), "{screen}");
+    assert!(screen.contains("Silence"));
+}
+
+#[test]
+fn snapshot_achievements() {
+    let mut h = Harness::with_game(with_achievements(), SpoilerLevel::Routes);
+    h.app.game_data.mark_step_completed("silent_sky_ch1_swimsuit", true);
+    h.press(&[KeyCode::Tab]);
+    assert_snapshot!(h.screen());
+}
+
+fn with_tips() -> GameData {
+    let tip = |id: &str, term: &str, chapter, routes: &[&str]| Tip {
+        id: id.to_string(),
+        term: term.to_string(),
+        chapter,
+        routes: routes.iter().map(|r| r.to_string()).collect(),
+        editions: vec![],
+    };
+    crate::load_title("chaos_head").unwrap().with_tips(vec![
+        tip("gigalomaniac", "Gigalomaniac", 1, &[]),
+        tip("di_sword", "Di-Sword", 2, &[]),
+        tip("crying", "Crying", 2, &["Crying Sky"]),
+    ])
+}
+
+#[test]
+fn tab_cycles_through_every_list() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Tab, KeyCode::Tab]);
+    assert!(matches!(h.app.current_view, View::Tips));
+    assert!(h.screen().contains("No TIPS are listed for this title yet."));
+
+    h.press(&[KeyCode::Tab]);
+    assert!(matches!(h.app.current_view, View::Collection));
+
+    h.press(&[KeyCode::Tab]);
+    assert!(matches!(h.app.current_view, View::RouteSelection));
+}
+
+#[test]
+fn tips_filter_by_chapter_and_toggle() {
+    let mut h = Harness::with_game(with_tips(), SpoilerLevel::Off);
+    h.press(&[KeyCode::Tab, KeyCode::Tab, KeyCode::Right, KeyCode::Right]);
+    assert_eq!(h.app.tip_chapter, Some(2));
+    assert!(!h.screen().contains("Gigalomaniac"));
+
+    h.press(&[KeyCode::Down, KeyCode::Char(' ')]);
+    assert!(h.app.game_data.is_tip_read("crying"));
+    assert!(h.app.unsaved_changes);
+
+    h.press(&[KeyCode::Right]);
+    assert_eq!(h.app.tip_chapter, None);
+    h.press(&[KeyCode::Left]);
+    assert_eq!(h.app.tip_chapter, Some(2));
+}
+
+#[test]
+fn snapshot_tips() {
+    let mut h = Harness::with_game(with_tips(), SpoilerLevel::Routes);
+    h.app.game_data.toggle_tip("gigalomaniac");
+    h.press(&[KeyCode::Tab, KeyCode::Tab]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn collection_lists_bundled_endings_and_filters_to_missing() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    let silent_sky: Vec<String> = h.app.game_data.routes[0].chapters.iter()
+        .flat_map(|chapter| &chapter.steps)
+        .map(|step| step.id.clone())
+        .collect();
+    for id in &silent_sky {
+        h.app.game_data.mark_step_completed(id, true);
+    }
+    h.press(&[KeyCode::Tab, KeyCode::Tab, KeyCode::Tab]);
+    assert!(matches!(h.app.current_view, View::Collection));
+    assert_eq!(h.app.visible_collection().len(), h.app.game_data.routes.len());
+
+    h.press(&[KeyCode::Char('m')]);
+    assert_eq!(h.app.visible_collection().len(), h.app.game_data.routes.len() - 1);
+    assert!(h.resize(80, 40).screen().contains("→ Replay Crying Sky"));
+}
+
+#[test]
+fn snapshot_collection() {
+    // Only the items below, without the bundled endings.
+    let mut game = crate::load_title("chaos_head").unwrap();
+    game.collection.clear();
+    let game = game.with_collection(vec![
+        CollectionItem {
+            id: "hospital_cg".to_string(),
+            name: "Hospital".to_string(),
+            kind: CollectionKind::Gallery,
+            unlock: Unlock::Step("silent_sky_ch2_hospital".to_string()),
+            editions: vec![],
+        },
+        CollectionItem {
+            id: "crying_sky_movie".to_string(),
+            name: "Crying Sky".to_string(),
+            kind: CollectionKind::Movie,
+            unlock: Unlock::Route("Crying Sky".to_string()),
+            editions: vec![],
+        },
+        CollectionItem {
+            id: "swimsuit_cg".to_string(),
+            name: "Swimsuit".to_string(),
+            kind: CollectionKind::Gallery,
+            unlock: Unlock::Step("silent_sky_ch1_swimsuit".to_string()),
+            editions: vec![],
+        },
+    ]);
+    let mut h = Harness::with_game(game, SpoilerLevel::Routes);
+    h.app.game_data.mark_step_completed("silent_sky_ch1_swimsuit", true);
+    h.press(&[KeyCode::Tab, KeyCode::Tab, KeyCode::Tab]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_route_selection() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_route_selection_with_spoilers() {
+    let mut h = Harness::new(SpoilerLevel::Routes);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_route_details() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Char(' '), KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_route_details_with_spoilers() {
+    let mut h = Harness::new(SpoilerLevel::Chapters);
+    h.press(&[KeyCode::Enter, KeyCode::Char(' ')]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_route_details_ascii() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.app.glyphs = Glyphs::new(GlyphMode::Ascii);
+    h.app.game_data.set_skipped("silent_sky_ch6_bad_ending", true);
+    h.press(&[KeyCode::Enter, KeyCode::Char(' '), KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_wide_layout() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.resize(160, 30);
+    h.press(&[KeyCode::Enter, KeyCode::Down, KeyCode::Down]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_step_details() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Enter]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_step_details_with_spoilers() {
+    let mut h = Harness::new(SpoilerLevel::Chapters);
+    h.press(&[KeyCode::Enter, KeyCode::Up, KeyCode::Up, KeyCode::Enter]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_help_popup() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Char('h')]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_step_details_editing_note() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Enter, KeyCode::Char('n')]);
+    for c in "Slot 3".chars() {
+        h.press(&[KeyCode::Char(c)]);
+    }
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn snapshot_save_slot_prompt() {
+    let mut h = Harness::new(SpoilerLevel::Off);
+    h.press(&[KeyCode::Enter, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Char(' '), KeyCode::Char('4')]);
+    assert_snapshot!(h.screen());
+}
+
+#[test]
+fn saving_keeps_progress_another_process_wrote() {
+    let dir = crate::testing::TempDir::new("tui-sync");
+    let path = dir.join("progress.json");
+
+    let mut app = App::new(crate::load_title("chaos_head").unwrap(), SpoilerLevel::Off);
+    app.store = Some(ProgressStore::at(path.clone()));
+    app.game_data.mark_step_completed("silent_sky_ch1_swimsuit", true);
+    app.save().unwrap();
+
+    // Written right after the TUI's own save, so it may share its mtime.
+    let mut served = crate::load_title("chaos_head").unwrap();
+    ProgressStore::at(path.clone()).load(&mut served).unwrap();
+    served.mark_step_completed("silent_sky_ch6_save", true);
+    ProgressStore::at(path.clone()).save(&served).unwrap();
+
+    app.game_data.mark_step_completed("silent_sky_ch1_swimsuit", false);
+    app.game_data.mark_step_completed("daydream_dt1", true);
+    app.save().unwrap();
+    assert!(app.game_data.step("silent_sky_ch6_save").unwrap().completed);
+
+    let mut saved = crate::load_title("chaos_head").unwrap();
+    ProgressStore::at(path).load(&mut saved).unwrap();
+    assert!(saved.step("silent_sky_ch6_save").unwrap().completed);
+    assert!(saved.step("daydream_dt1").unwrap().completed);
+    assert!(!saved.step("silent_sky_ch1_swimsuit").unwrap().completed);
+}